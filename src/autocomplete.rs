@@ -3,12 +3,18 @@ use std::collections::{HashSet, HashMap};
 use regex::Regex;
 use once_cell::sync::Lazy;
 use crate::schema_cache::{SchemaCache, ObjectType};
+use crate::query_backend::SessionContext;
 
 // Compile regex once at startup
 static TABLE_PATTERN: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"\b([a-zA-Z_][a-zA-Z0-9_]*\.){2}[a-zA-Z_][a-zA-Z0-9_]*\b").unwrap()
 });
 
+// `FROM orders` / `JOIN sales.orders` - resolved against the session's database and schema
+static UNQUALIFIED_TABLE_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:FROM|JOIN)\s+([a-zA-Z_][a-zA-Z0-9_]*(?:\.[a-zA-Z_][a-zA-Z0-9_]*)?)\b").unwrap()
+});
+
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Suggestion {
@@ -182,6 +188,7 @@ impl Autocomplete {
         buffer: &str,
         caret: usize,
         cache: Option<&SchemaCache>,
+        session: Option<&SessionContext>,
     ) {
 
         // Quick hash of buffer for cache invalidation (use length + first/last 100 chars)
@@ -223,8 +230,18 @@ impl Autocomplete {
         let prefix_parts: Vec<&str> = word.split('.').collect();
         let context_depth = prefix_parts.len();
         
+        let current_db = session.and_then(|s| s.database.as_deref());
+        let current_schema = session.and_then(|s| s.schema.as_deref());
+
+        // "schema.?" where the schema belongs to the session's database
+        let schema_in_current_db = context_depth == 2
+            && cache.zip(current_db).is_some_and(|(cache, db)| {
+                !cache.databases.contains_key(prefix_parts[0])
+                    && cache.databases.get(db).is_some_and(|d| d.schemas.contains_key(&prefix_parts[0].to_uppercase()))
+            });
+
         // Check if we have a dot-qualified name and if the qualifier is valid
-        let treat_as_simple_word = if context_depth > 1 {
+        let treat_as_simple_word = if context_depth > 1 && !schema_in_current_db {
             // Check if the first part is a recognized database
             if let Some(cache) = cache {
                 !cache.databases.contains_key(prefix_parts[0])
@@ -254,12 +271,33 @@ impl Autocomplete {
             if let Some(cache) = cache {
                 suggestions.extend(get_database_suggestions(search_word, cache));
             }
+
+            // 3. Schemas of the current database and objects of the current
+            //    schema, completed unqualified
+            if let (Some(cache), Some(db_name)) = (cache, current_db) {
+                let mut local = get_schema_suggestions_for_db(db_name, search_word, cache);
+                if let Some(schema_name) = current_schema {
+                    local.extend(get_object_suggestions_for_schema(db_name, schema_name, search_word, cache));
+                }
+                suggestions.extend(local.into_iter().map(|s| Suggestion { text: s.display_text.clone(), ..s }));
+            }
             
-            // 3. Add column suggestions from referenced tables
+            // 4. Add column suggestions from referenced tables
             if let Some(cache) = cache {
-                suggestions.extend(get_column_suggestions(buffer, search_word, cache));
+                suggestions.extend(get_column_suggestions(buffer, search_word, cache, session));
             }
             
+        } else if schema_in_current_db {
+            // One dot, but the qualifier is a schema of the current database
+            if let (Some(cache), Some(db_name)) = (cache, current_db) {
+                let schema_name = prefix_parts[0].to_uppercase();
+                suggestions.extend(
+                    get_object_suggestions_for_schema(db_name, &schema_name, prefix_parts[1], cache)
+                        .into_iter()
+                        .map(|s| Suggestion { text: format!("{}.{}", prefix_parts[0], s.display_text), ..s }),
+                );
+            }
+
         } else if context_depth == 2 {
             // One dot - format is "database.?"
             // Only suggest schemas for that database
@@ -539,6 +577,42 @@ fn get_columns_for_table(
     Vec::new()
 }
 
+// `table` and `schema.table` after FROM / JOIN, qualified with the session's
+// database and schema
+fn unqualified_table_references(buffer: &str, session: Option<&SessionContext>) -> HashSet<String> {
+    let mut refs = HashSet::new();
+    let Some(db_name) = session.and_then(|s| s.database.as_deref()) else {
+        return refs;
+    };
+    let current_schema = session.and_then(|s| s.schema.as_deref());
+
+    const MAX_SCAN_SIZE: usize = 10_000;
+    let scan_buffer = if buffer.len() > MAX_SCAN_SIZE {
+        let mut start = buffer.len() - MAX_SCAN_SIZE;
+        while !buffer.is_char_boundary(start) {
+            start += 1;
+        }
+        &buffer[start..]
+    } else {
+        buffer
+    };
+
+    for cap in UNQUALIFIED_TABLE_PATTERN.captures_iter(scan_buffer).take(20) {
+        let name = cap[1].to_uppercase();
+        match name.split_once('.') {
+            Some((schema, table)) => {
+                refs.insert(format!("{}.{}.{}", db_name, schema, table));
+            }
+            None => {
+                if let Some(schema) = current_schema {
+                    refs.insert(format!("{}.{}.{}", db_name, schema, name));
+                }
+            }
+        }
+    }
+    refs
+}
+
 // Helper function to extract word at position (including dots for qualified names)
 fn get_word_at_position(buffer: &str, pos: usize) -> (&str, usize, usize) {
     if pos > buffer.len() {
@@ -683,12 +757,18 @@ fn get_schema_suggestions(prefix: &str, cache: &SchemaCache) -> Vec<Suggestion>
 }
 
 // Get column suggestions from referenced tables in the buffer
-fn get_column_suggestions(buffer: &str, prefix: &str, cache: &SchemaCache) -> Vec<Suggestion> {
+fn get_column_suggestions(
+    buffer: &str,
+    prefix: &str,
+    cache: &SchemaCache,
+    session: Option<&SessionContext>,
+) -> Vec<Suggestion> {
     let mut suggestions = Vec::new();
     let prefix_upper = prefix.to_uppercase();
     
     // Use the extract method
-    let referenced_tables = Autocomplete::extract_table_references(buffer);
+    let mut referenced_tables = Autocomplete::extract_table_references(buffer);
+    referenced_tables.extend(unqualified_table_references(buffer, session));
     
     if referenced_tables.is_empty() {
         return suggestions;
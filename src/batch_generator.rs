@@ -12,6 +12,7 @@ pub struct BatchScriptConfig {
     pub verbose: bool,
     pub last_query_only: bool,
     pub auto_dismiss: bool,
+    pub profile: Option<String>,
 }
 
 impl BatchScriptConfig {
@@ -29,6 +30,7 @@ impl BatchScriptConfig {
             verbose: true,
             last_query_only: true,
             auto_dismiss: false,    // Add this field
+            profile: None,
         }
     }
 }
@@ -73,6 +75,9 @@ fn generate_windows_batch(config: &BatchScriptConfig) -> String {
     script.push_str(&format!("--sql-file \"{}\" ", config.sql_file.display()));
     script.push_str(&format!("--output-dir \"{}\" ", config.output_dir.display()));
     script.push_str(&format!("--format {} ", config.output_format));
+    if let Some(profile) = &config.profile {
+        script.push_str(&format!("--profile \"{}\" ", profile));
+    }
     
     if config.exit_on_error {
         script.push_str("--exit-on-error ");
@@ -119,6 +124,9 @@ fn generate_unix_script(config: &BatchScriptConfig) -> String {
     script.push_str(&format!("    --sql-file \"{}\" \\\n", config.sql_file.display()));
     script.push_str(&format!("    --output-dir \"{}\" \\\n", config.output_dir.display()));
     script.push_str(&format!("    --format {} ", config.output_format));
+    if let Some(profile) = &config.profile {
+        script.push_str(&format!("\\\n    --profile \"{}\" ", profile));
+    }
     
     if config.exit_on_error {
         script.push_str("\\\n    --exit-on-error ");
@@ -159,6 +167,7 @@ pub struct BatchGeneratorDialog {
     pub verbose: bool,
     pub last_query_only: bool,
     pub auto_dismiss: bool,
+    pub profile: Option<String>,  // active connection profile, passed through to the script
     pub field_index: usize,
     pub message: Option<String>,
     clipboard: ClipboardContext,
@@ -181,6 +190,7 @@ impl BatchGeneratorDialog {
             verbose: true,
             last_query_only: true,   // Default to true as requested
             auto_dismiss: false,     // Add this field
+            profile: None,
             field_index: 0,
             message: None,
             clipboard: ClipboardContext::new().unwrap(),
@@ -266,6 +276,7 @@ impl BatchGeneratorDialog {
                     verbose: self.verbose,
                     last_query_only: self.last_query_only,
                     auto_dismiss: self.auto_dismiss,
+                    profile: self.profile.clone(),
                 };
                 
                 match generate_batch_script(&config) {
@@ -1,19 +1,26 @@
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 use anyhow::Result;
-use crate::workspace::{DbWorkerRequest, DbWorkerResponse, start_db_worker};
+use crate::workspace::{DbWorkerRequest, DbWorkerResponse, WorkerOptions, start_db_worker};
+use crate::query_backend::QueryBackend;
 use crate::results::{ResultsContent};
-use crate::results_export::export_entire_result_set;
+use crate::results_export::write_entire_result_set;
+use crate::column_type::{ColumnType, TypedValue};
+use crate::cell_value::ValueOptions;
 
 pub struct BatchConfig {
     pub sql_file: PathBuf,
     pub output_dir: PathBuf,
     pub output_format: OutputFormat,
-    pub connection_string: String,
     pub exit_on_error: bool,
     pub verbose: bool,
-    pub last_query_only: bool
+    pub last_query_only: bool,
+    /// Cancel any statement running longer than this
+    pub statement_timeout: Option<Duration>,
+    /// Binary format and spill size from Frost.toml
+    pub values: ValueOptions,
 }
 
 #[derive(Clone, Copy)]
@@ -35,7 +42,56 @@ impl OutputFormat {
     }
 }
 
-pub fn run_batch_mode(config: BatchConfig) -> Result<()> {
+/// Values for the connection string's `${prompt:…}` placeholders: taken from
+/// `FROST_PROMPT_<LABEL>` when set, otherwise read from stdin (without echo
+/// when stdin is a terminal)
+pub fn read_secret_prompts(conn_str: &str) -> Result<()> {
+    use std::io::{BufRead, IsTerminal, Write};
+
+    for label in crate::secrets::missing_prompts(conn_str) {
+        let stdin = std::io::stdin();
+        let value = if stdin.is_terminal() {
+            eprint!("{}: ", label);
+            let _ = std::io::stderr().flush();
+            read_masked_line()?
+        } else {
+            let mut line = String::new();
+            stdin.lock().read_line(&mut line)?;
+            line.trim_end_matches(['\r', '\n']).to_string()
+        };
+        crate::secrets::set_prompt(&label, value);
+    }
+    Ok(())
+}
+
+fn read_masked_line() -> Result<String> {
+    use crossterm::event::{read, Event, KeyCode, KeyEventKind, KeyModifiers};
+
+    crossterm::terminal::enable_raw_mode()?;
+    let mut value = String::new();
+    let outcome = loop {
+        match read() {
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => match key.code {
+                KeyCode::Enter => break Ok(()),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    break Err(anyhow::anyhow!("Cancelled"));
+                }
+                KeyCode::Backspace => {
+                    value.pop();
+                }
+                KeyCode::Char(ch) => value.push(ch),
+                _ => {}
+            },
+            Ok(_) => {}
+            Err(e) => break Err(e.into()),
+        }
+    };
+    crossterm::terminal::disable_raw_mode()?;
+    eprintln!();
+    outcome.map(|_| value)
+}
+
+pub fn run_batch_mode<B: QueryBackend + 'static>(config: BatchConfig, backend: B) -> Result<()> {
     if config.verbose {
         println!("Frost Batch Mode");
         println!("==================");
@@ -68,7 +124,13 @@ pub fn run_batch_mode(config: BatchConfig) -> Result<()> {
     }
 
     // Start DB worker
-    let (db_req_tx, db_resp_rx, _) = start_db_worker(config.connection_string.clone());
+    // No global row cap for exports; `--@limit` hints in the script still apply
+    let options = WorkerOptions {
+        statement_timeout: config.statement_timeout,
+        values: config.values,
+        ..WorkerOptions::default()
+    };
+    let (db_req_tx, db_resp_rx, _) = start_db_worker(backend, options, true);
 
     // Execute queries
     let queries_with_context: Vec<(String, String)> = queries
@@ -81,37 +143,104 @@ pub fn run_batch_mode(config: BatchConfig) -> Result<()> {
         .collect();
 
     let total_queries = queries_with_context.len();
-    let _ = db_req_tx.send(DbWorkerRequest::RunQueries(queries_with_context));
+    let _ = db_req_tx.send(DbWorkerRequest::RunQueries {
+        queries: queries_with_context,
+        continue_on_error: !config.exit_on_error,
+    });
 
     // Process results
     let mut results_count = 0;
     let mut errors_count = 0;
     let start_time = Instant::now();
     let mut last_result: Option<(usize, ResultsContent)> = None;  // Store last result
+    let mut query_ids: Vec<(usize, &str, String)> = Vec::new();   // (query, status, id) for query_ids.csv
+    let mut result_sets: HashMap<usize, usize> = HashMap::new();  // result sets saved so far, per query
 
     while results_count + errors_count < total_queries {
-        match db_resp_rx.recv_timeout(Duration::from_secs(300)) {
+        // The worker cancels statements that exceed the statement timeout itself,
+        // so wait for as long as it takes
+        match db_resp_rx.recv() {
             Ok(DbWorkerResponse::Connected) => {
                 // Connection established, continue
                 continue;
             }
+            Ok(DbWorkerResponse::Disconnected { message, retry_in: Some(wait) }) => {
+                eprintln!("Connection failed, retrying in {}s: {}", wait.as_secs(), message);
+                continue;
+            }
+            Ok(DbWorkerResponse::Disconnected { message, retry_in: None }) => {
+                let _ = db_req_tx.send(DbWorkerRequest::Quit);
+                return Err(anyhow::anyhow!("Could not connect to the database: {}", message));
+            }
+            Ok(DbWorkerResponse::Reconnecting { attempt }) => {
+                if config.verbose {
+                    println!("Reconnecting (attempt {})...", attempt);
+                }
+                continue;
+            }
+            Ok(DbWorkerResponse::SessionContext(_)) => {
+                // only shown in the interactive status bar
+                continue;
+            }
+            Ok(DbWorkerResponse::ParkedSealed { .. }) => {
+                // only sent after the run, when quitting closes a capped cursor
+                continue;
+            }
+            Ok(DbWorkerResponse::TransactionModeChanged { .. })
+            | Ok(DbWorkerResponse::TransactionEnded { .. })
+            | Ok(DbWorkerResponse::TransactionError { .. }) => {
+                // batch runs in autocommit and never asks for these
+                continue;
+            }
+            Ok(DbWorkerResponse::Idle) => {
+                // the run is over, possibly stopped early by a cancel or lost connection
+                break;
+            }
             Ok(DbWorkerResponse::QueryStarted { query_idx: _, query_context, .. }) => {
                 if config.verbose {
                     println!("\nExecuting {}", query_context);
                 }
             }
-            Ok(DbWorkerResponse::QueryFinished { query_idx, elapsed, mut result }) => {
+            Ok(DbWorkerResponse::QueryStreaming { .. }) | Ok(DbWorkerResponse::QueryProgress { .. }) => {
+                // Partial results are for the interactive grid; batch waits for the end
+            }
+            Ok(DbWorkerResponse::QueryNextResultSet { query_idx, mut result, .. }) => {
+                // One of several result sets: query_003_1.csv, query_003_2.csv, ...
+                let set = result_sets.entry(query_idx).or_insert(0);
+                *set += 1;
+                let stem = format!("query_{:03}_{}", query_idx + 1, set);
+                if config.last_query_only {
+                    last_result = Some((query_idx, result));
+                } else if let Err(e) = save_result(&config, &stem, &mut result) {
+                    eprintln!("Error saving result set {} of query {}: {}", set, query_idx + 1, e);
+                    if config.exit_on_error {
+                        return Err(e);
+                    }
+                }
+            }
+            Ok(DbWorkerResponse::QueryFinished { query_idx, elapsed, mut result, query_id })
+            | Ok(DbWorkerResponse::QueryTruncated { query_idx, elapsed, mut result, query_id, .. }) => {
                 results_count += 1;
                 if config.verbose {
                     println!("  Completed in {:?}", elapsed);
+                    if let Some(id) = &query_id {
+                        println!("  Query ID: {}", id);
+                    }
+                }
+                if let Some(id) = query_id {
+                    query_ids.push((query_idx, "ok", id));
                 }
                 
+                let stem = match result_sets.get(&query_idx) {
+                    Some(set) => format!("query_{:03}_{}", query_idx + 1, set + 1),
+                    None => format!("query_{:03}", query_idx + 1),
+                };
                 if config.last_query_only {
                     // Store the result for later
                     last_result = Some((query_idx, result));
                 } else {
                     // Save results immediately
-                    if let Err(e) = save_result(&config, query_idx, &mut result) {
+                    if let Err(e) = save_result(&config, &stem, &mut result) {
                         eprintln!("Error saving results for query {}: {}", query_idx + 1, e);
                         if config.exit_on_error {
                             return Err(e);
@@ -119,16 +248,29 @@ pub fn run_batch_mode(config: BatchConfig) -> Result<()> {
                     }
                 }
             }
-            Ok(DbWorkerResponse::QueryError { query_idx, elapsed: _, message }) => {
+            Ok(DbWorkerResponse::QueryTimedOut { query_idx, elapsed: _, timeout }) => {
+                errors_count += 1;
+                eprintln!("Query {} timed out after {:?} and was cancelled", query_idx + 1, timeout);
+                if config.exit_on_error {
+                    return Err(anyhow::anyhow!("Query {} timed out", query_idx + 1));
+                }
+            }
+            Ok(DbWorkerResponse::QueryError { query_idx, elapsed: _, message, query_id }) => {
                 errors_count += 1;
-                eprintln!("Error in query {}: {}", query_idx + 1, message);
+                match &query_id {
+                    Some(id) => eprintln!("Error in query {} (query ID {}): {}", query_idx + 1, id, message),
+                    None => eprintln!("Error in query {}: {}", query_idx + 1, message),
+                }
+                if let Some(id) = query_id {
+                    query_ids.push((query_idx, "error", id));
+                }
                 if config.exit_on_error {
                     return Err(anyhow::anyhow!("Query failed: {}", message));
                 }
             }
             Err(_) => {
-                eprintln!("Timeout waiting for query results");
-                return Err(anyhow::anyhow!("Query execution timeout"));
+                eprintln!("Database worker stopped before all queries finished");
+                return Err(anyhow::anyhow!("Database worker stopped"));
             }
         }
     }
@@ -136,7 +278,7 @@ pub fn run_batch_mode(config: BatchConfig) -> Result<()> {
     // If last_query_only, save only the last successful result
     if config.last_query_only {
         if let Some((_query_idx, mut result)) = last_result {
-            if let Err(e) = save_result(&config, 0, &mut result) {  // Save as query_001
+            if let Err(e) = save_result(&config, "query_001", &mut result) {
                 eprintln!("Error saving last query result: {}", e);
                 if config.exit_on_error {
                     return Err(e);
@@ -145,6 +287,18 @@ pub fn run_batch_mode(config: BatchConfig) -> Result<()> {
         }
     }
 
+    // Query IDs for looking the statements up in Snowflake's query history
+    if !query_ids.is_empty() {
+        let mut csv = String::from("query,status,query_id\n");
+        for (query_idx, status, id) in &query_ids {
+            csv.push_str(&format!("query_{:03},{},{}\n", query_idx + 1, status, id));
+        }
+        let path = config.output_dir.join("query_ids.csv");
+        if let Err(e) = fs::write(&path, csv) {
+            eprintln!("Error writing {}: {}", path.display(), e);
+        }
+    }
+
     let total_elapsed = start_time.elapsed();
     if config.verbose {
         println!("\nBatch execution completed");
@@ -159,38 +313,38 @@ pub fn run_batch_mode(config: BatchConfig) -> Result<()> {
 }
 
 // Update save_result to handle XLSX format:
-fn save_result(config: &BatchConfig, query_idx: usize, result: &mut ResultsContent) -> Result<()> {
+/// Write a result to `<stem>.<ext>` (`<stem>_info.txt` for messages)
+fn save_result(config: &BatchConfig, stem: &str, result: &mut ResultsContent) -> Result<()> {
     match result {
-        ResultsContent::Table { headers, tile_store } => {
-            // Fetch all rows
-            let rows = tile_store.get_rows(0, tile_store.nrows)?;
-            
-            let filename = format!("query_{:03}.{}", 
-                query_idx + 1, 
-                config.output_format.extension()
-            );
+        ResultsContent::Table { headers, column_types, tile_store } => {
+            let filename = format!("{}.{}", stem, config.output_format.extension());
             let output_path = config.output_dir.join(filename);
             
+            // Fetch all rows, oversized values in full
+            let mut all_rows = || tile_store.get_full_rows(0, tile_store.nrows);
+            
             match config.output_format {
                 OutputFormat::Csv => {
-                    let csv_content = export_entire_result_set(headers, &rows);
-                    fs::write(&output_path, csv_content)?;
+                    // Streamed straight from the tiles
+                    let mut out = std::io::BufWriter::new(fs::File::create(&output_path)?);
+                    write_entire_result_set(&mut out, headers, tile_store)?;
+                    std::io::Write::flush(&mut out)?;
                 }
                 OutputFormat::Json => {
-                    let json_data = rows_to_json(headers, &rows);
+                    let json_data = rows_to_json(headers, column_types, &all_rows()?);
                     fs::write(&output_path, serde_json::to_string_pretty(&json_data)?)?;
                 }
                 OutputFormat::Text => {
-                    let text_content = format_as_table(headers, &rows);
+                    let text_content = format_as_table(headers, &all_rows()?);
                     fs::write(&output_path, text_content)?;
                 }
                 OutputFormat::Xlsx => {
-                    write_xlsx(&output_path, headers, &rows)?;
+                    write_xlsx(&output_path, headers, column_types, &all_rows()?)?;
                 }
             }
             
             if config.verbose {
-                println!("  Saved {} rows to {}", rows.len(), output_path.display());
+                println!("  Saved {} rows to {}", tile_store.nrows, output_path.display());
             }
         }
         ResultsContent::Info { message } => {
@@ -198,46 +352,56 @@ fn save_result(config: &BatchConfig, query_idx: usize, result: &mut ResultsConte
                 println!("  Info: {}", message);
             }
             // Optionally save info messages
-            let filename = format!("query_{:03}_info.txt", query_idx + 1);
+            let filename = format!("{}_info.txt", stem);
             let output_path = config.output_dir.join(filename);
             fs::write(&output_path, message)?;
         }
-        ResultsContent::Error { message: _, .. } => {
+        ResultsContent::Error { message: _, .. } | ResultsContent::TimedOut { .. } => {
             // Already handled in the response processing
         }
-        ResultsContent::Pending => {
+        ResultsContent::Pending | ResultsContent::Queued { .. } => {
             // Should not happen in batch mode
         }
     }
     Ok(())
 }
 
-fn write_xlsx(path: &Path, headers: &[String], rows: &[Vec<String>]) -> Result<()> {
-    use rust_xlsxwriter::{Workbook, Format};
+fn write_xlsx(path: &Path, headers: &[String], column_types: &[ColumnType], rows: &[Vec<String>]) -> Result<()> {
+    use rust_xlsxwriter::{Workbook, Format, ExcelDateTime};
     
     let mut workbook = Workbook::new();
     let worksheet = workbook.add_worksheet();
     
     // Create a bold format for headers
     let bold_format = Format::new().set_bold();
+    let date_format = Format::new().set_num_format("yyyy-mm-dd");
+    let timestamp_format = Format::new().set_num_format("yyyy-mm-dd hh:mm:ss");
     
     // Write headers with bold format
     for (col, header) in headers.iter().enumerate() {
         worksheet.write_with_format(0, col as u16, header, &bold_format)?;
     }
     
-    // Write data rows
+    // Write data rows, typed by column
     for (row_idx, row) in rows.iter().enumerate() {
+        let xl_row = row_idx as u32 + 1;
         for (col_idx, cell) in row.iter().enumerate() {
-            if cell == crate::tile_rowstore::NULL_SENTINEL {
+            let xl_col = col_idx as u16;
+            let col_type = column_types.get(col_idx).copied().unwrap_or(ColumnType::Text);
+            match col_type.parse(cell) {
                 // Leave NULL cells empty in Excel
-                continue;
-            }
-            // Try to parse as number first
-            if let Ok(num) = cell.parse::<f64>() {
-                worksheet.write(row_idx as u32 + 1, col_idx as u16, num)?;
-            } else {
-                worksheet.write(row_idx as u32 + 1, col_idx as u16, cell)?;
+                TypedValue::Null => {}
+                TypedValue::Int(i) => { worksheet.write(xl_row, xl_col, i as f64)?; }
+                TypedValue::Num(n) => { worksheet.write(xl_row, xl_col, n)?; }
+                TypedValue::Bool(b) => { worksheet.write_boolean(xl_row, xl_col, b)?; }
+                TypedValue::Date(_) | TypedValue::Timestamp(_) => {
+                    let format = if col_type == ColumnType::Date { &date_format } else { &timestamp_format };
+                    match ExcelDateTime::parse_from_str(cell) {
+                        Ok(dt) => { worksheet.write_datetime_with_format(xl_row, xl_col, &dt, format)?; }
+                        Err(_) => { worksheet.write(xl_row, xl_col, cell)?; }
+                    }
+                }
+                TypedValue::Text(_) => { worksheet.write(xl_row, xl_col, cell)?; }
             }
         }
     }
@@ -251,20 +415,24 @@ fn write_xlsx(path: &Path, headers: &[String], rows: &[Vec<String>]) -> Result<(
     Ok(())
 }
 
-fn rows_to_json(headers: &[String], rows: &[Vec<String>]) -> serde_json::Value {
-    use serde_json::{json, Value, Map};
+fn rows_to_json(headers: &[String], column_types: &[ColumnType], rows: &[Vec<String>]) -> serde_json::Value {
+    use serde_json::{json, Value, Map, Number};
     
     let mut result = Vec::new();
     for row in rows {
         let mut obj = Map::new();
         for (i, header) in headers.iter().enumerate() {
+            let col_type = column_types.get(i).copied().unwrap_or(ColumnType::Text);
             let value = row.get(i)
-                .map(|v| {
-                    if v == crate::tile_rowstore::NULL_SENTINEL {
-                        Value::Null
-                    } else {
-                        Value::String(v.clone())
-                    }
+                .map(|v| match col_type.parse(v) {
+                    TypedValue::Null => Value::Null,
+                    TypedValue::Int(n) => Value::from(n),
+                    // DECIMAL values and integers past i64 keep their exact digits as text
+                    TypedValue::Num(n) if col_type == ColumnType::Float => Number::from_f64(n)
+                        .map(Value::Number)
+                        .unwrap_or_else(|| Value::String(v.clone())),
+                    TypedValue::Bool(b) => Value::Bool(b),
+                    _ => Value::String(v.clone()),
                 })
                 .unwrap_or(Value::Null);
             obj.insert(header.clone(), value);
@@ -15,6 +15,10 @@ use Frost::{
 struct Args {
     #[arg(long)]
     data_dir: Option<PathBuf>,
+
+    /// Connection profile from Frost.toml
+    #[arg(long)]
+    profile: Option<String>,
 }
 
 fn main() -> Result<()> {
@@ -63,11 +67,13 @@ fn main() -> Result<()> {
     
     // Load configuration
     let config = Config::load()?;
+    let (_, profile) = config.profile(args.profile.as_deref())?;
     
     let env = create_environment_v3()
         .map_err(|e| anyhow::anyhow!("Failed to create ODBC environment: {:?}", e))?;
-    let conn = env.connect_with_connection_string(&config.connection_string)
-        .map_err(|e| anyhow::anyhow!("Failed to connect: {:?}", e))?;
+    let expanded = Frost::secrets::expand(&profile.effective_connection_string())?;
+    let conn = env.connect_with_connection_string(&expanded.text)
+        .map_err(|e| anyhow::anyhow!("Failed to connect: {}", expanded.redact(&format!("{:?}", e))))?;
     println!("Connected successfully!");
     
     // Load or create cache
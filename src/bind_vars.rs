@@ -0,0 +1,322 @@
+//! Placeholders (`:name`, `$name`, `@name`) in statements about to run.
+//!
+//! `start_execute` scans the statements for placeholders nothing in the run
+//! binds, the bind dialog collects a value for each, and the values are
+//! spliced back in as quoted SQL literals before the worker sees the text.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use chrono::NaiveDate;
+
+use crate::column_type::{parse_timestamp, DATE_FORMAT};
+use crate::syntax::{step, ParseState, Step};
+
+/// How a value typed into the bind dialog is turned into a literal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BindKind {
+    #[default]
+    Auto,
+    Text,
+    Number,
+    Date,
+    Timestamp,
+}
+
+impl BindKind {
+    pub fn next(self) -> Self {
+        match self {
+            BindKind::Auto => BindKind::Text,
+            BindKind::Text => BindKind::Number,
+            BindKind::Number => BindKind::Date,
+            BindKind::Date => BindKind::Timestamp,
+            BindKind::Timestamp => BindKind::Auto,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BindKind::Auto => "auto",
+            BindKind::Text => "text",
+            BindKind::Number => "number",
+            BindKind::Date => "date",
+            BindKind::Timestamp => "timestamp",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BindValue {
+    pub text: String,
+    pub kind: BindKind,
+}
+
+impl BindValue {
+    /// What an `Auto` value will be sent as; shown next to the input
+    pub fn hint(&self) -> &'static str {
+        if self.kind != BindKind::Auto {
+            return self.kind.label();
+        }
+        let v = self.text.trim();
+        if v.is_empty() {
+            "unchanged"
+        } else if v.eq_ignore_ascii_case("null") {
+            "null"
+        } else if is_number(v) {
+            "number"
+        } else if v.eq_ignore_ascii_case("true") || v.eq_ignore_ascii_case("false") {
+            "boolean"
+        } else {
+            "text"
+        }
+    }
+
+    /// The SQL literal for this value, or `None` when an empty `Auto` value
+    /// should leave the placeholder as written (e.g. a session variable).
+    pub fn to_literal(&self, backslash_escapes: bool) -> Result<Option<String>, String> {
+        let v = self.text.trim();
+        match self.kind {
+            BindKind::Auto => Ok(match self.hint() {
+                "unchanged" => None,
+                "null" => Some("NULL".to_string()),
+                "number" => Some(v.to_string()),
+                "boolean" => Some(v.to_ascii_uppercase()),
+                _ => Some(quote_literal(&self.text, backslash_escapes)),
+            }),
+            BindKind::Text => Ok(Some(quote_literal(&self.text, backslash_escapes))),
+            BindKind::Number if is_number(v) => Ok(Some(v.to_string())),
+            BindKind::Number => Err(format!("'{}' is not a number", v)),
+            BindKind::Date if NaiveDate::parse_from_str(v, DATE_FORMAT).is_ok() => {
+                Ok(Some(quote_literal(v, backslash_escapes)))
+            }
+            BindKind::Date => Err(format!("'{}' is not a date (YYYY-MM-DD)", v)),
+            BindKind::Timestamp if parse_timestamp(v).is_some() => Ok(Some(quote_literal(v, backslash_escapes))),
+            BindKind::Timestamp => Err(format!("'{}' is not a timestamp (YYYY-MM-DD HH:MM:SS)", v)),
+        }
+    }
+}
+
+/// Plain decimal numbers only, so nothing but digits, sign, point and exponent
+/// ever reaches the SQL unquoted.
+fn is_number(s: &str) -> bool {
+    s.parse::<f64>().is_ok()
+        && s.bytes().all(|b| b.is_ascii_digit() || matches!(b, b'-' | b'+' | b'.' | b'e' | b'E'))
+        && s.bytes().any(|b| b.is_ascii_digit())
+}
+
+/// Single-quote `s`, doubling embedded quotes. Snowflake also treats `\` as an
+/// escape inside string literals, so backslashes are doubled there too.
+pub fn quote_literal(s: &str, backslash_escapes: bool) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('\'');
+    for ch in s.chars() {
+        match ch {
+            '\'' => out.push_str("''"),
+            '\\' if backslash_escapes => out.push_str("\\\\"),
+            _ => out.push(ch),
+        }
+    }
+    out.push('\'');
+    out
+}
+
+/// One placeholder occurrence; `name` keeps its sigil (`:id`, `$region`).
+#[derive(Debug, Clone)]
+pub struct Placeholder {
+    pub name: String,
+    pub range: Range<usize>,
+}
+
+fn is_ident_start(b: u8) -> bool {
+    b.is_ascii_alphabetic() || b == b'_'
+}
+
+fn is_ident_char(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b == b'$'
+}
+
+/// End of the placeholder starting at `i`, if there is one. `::` casts,
+/// `col:field` paths, `@@system` variables and `$1` columns don't count.
+fn placeholder_end(bytes: &[u8], i: usize) -> Option<usize> {
+    if !matches!(bytes[i], b':' | b'$' | b'@') {
+        return None;
+    }
+    if i > 0 {
+        let prev = bytes[i - 1];
+        if is_ident_char(prev) || matches!(prev, b':' | b'@' | b'.' | b'"' | b')' | b']') {
+            return None;
+        }
+    }
+    if !bytes.get(i + 1).is_some_and(|&b| is_ident_start(b)) {
+        return None;
+    }
+    let mut end = i + 2;
+    while end < bytes.len() && is_ident_char(bytes[end]) {
+        end += 1;
+    }
+    Some(end)
+}
+
+/// The statement with leading whitespace and comments removed
+fn skip_leading_comments(sql: &str) -> &str {
+    let mut s = sql.trim_start();
+    loop {
+        if let Some(rest) = s.strip_prefix("--") {
+            s = rest.split_once('\n').map(|(_, r)| r).unwrap_or("").trim_start();
+        } else if let Some(rest) = s.strip_prefix("/*") {
+            s = rest.split_once("*/").map(|(_, r)| r).unwrap_or("").trim_start();
+        } else {
+            return s;
+        }
+    }
+}
+
+fn first_word(sql: &str) -> String {
+    skip_leading_comments(sql)
+        .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .next()
+        .unwrap_or("")
+        .to_ascii_uppercase()
+}
+
+/// Snowflake Scripting blocks use `:name` for their own variables
+fn is_script_block(sql: &str) -> bool {
+    match first_word(sql).as_str() {
+        "DECLARE" => true,
+        "BEGIN" => !skip_leading_comments(sql).to_ascii_uppercase().contains("TRANSACTION"),
+        _ => false,
+    }
+}
+
+/// `@name` right after these (or anywhere in a file-transfer command) is a stage
+fn is_stage_reference(sql: &str, at: usize, end: usize) -> bool {
+    if matches!(first_word(sql).as_str(), "PUT" | "GET" | "LIST" | "LS" | "REMOVE" | "RM") {
+        return true;
+    }
+    if sql.as_bytes().get(end) == Some(&b'/') {
+        return true;
+    }
+    let before = sql[..at].trim_end();
+    let word = before.rsplit(|c: char| c.is_whitespace() || c == '(').next().unwrap_or("");
+    word.eq_ignore_ascii_case("FROM") || word.eq_ignore_ascii_case("INTO")
+}
+
+/// Placeholders in one statement, outside strings, comments and `$$` bodies
+pub fn find_placeholders(sql: &str) -> Vec<Placeholder> {
+    let mut out = Vec::new();
+    if is_script_block(sql) {
+        return out;
+    }
+
+    let bytes = sql.as_bytes();
+    let mut state = ParseState::Normal;
+    let mut i = 0;
+    while i < bytes.len() {
+        if state == ParseState::Normal {
+            if let Some(end) = placeholder_end(bytes, i) {
+                if bytes[i] != b'@' || !is_stage_reference(sql, i, end) {
+                    out.push(Placeholder { name: sql[i..end].to_string(), range: i..end });
+                }
+                i = end;
+                continue;
+            }
+        }
+        match step(bytes, i, &mut state) {
+            (_, Step::Eof) => break,
+            (next, _) => i = next,
+        }
+    }
+    out
+}
+
+/// Session variables assigned by `SET name = …` / `SET (a, b) = …`
+pub fn session_variables_set(sql: &str) -> Vec<String> {
+    let stmt = skip_leading_comments(sql);
+    if first_word(stmt) != "SET" {
+        return Vec::new();
+    }
+    let rest = stmt[3..].trim_start();
+    let targets = match rest.strip_prefix('(') {
+        Some(list) => list.split(')').next().unwrap_or(""),
+        None => rest.split('=').next().unwrap_or(""),
+    };
+    targets
+        .split(',')
+        .map(|n| n.trim().to_ascii_uppercase())
+        .filter(|n| !n.is_empty())
+        .collect()
+}
+
+/// Placeholders across `queries` that still need a value, in first-seen order
+pub fn unbound_placeholders(queries: &[String]) -> Vec<String> {
+    let set_in_run: Vec<String> = queries.iter().flat_map(|q| session_variables_set(q)).collect();
+    let mut names: Vec<String> = Vec::new();
+    for q in queries {
+        for p in find_placeholders(q) {
+            if p.name.starts_with('$') && set_in_run.contains(&p.name[1..].to_ascii_uppercase()) {
+                continue;
+            }
+            if !names.contains(&p.name) {
+                names.push(p.name);
+            }
+        }
+    }
+    names
+}
+
+/// A placeholder `substitute` replaced: its byte range in the statement as
+/// written and the length of the literal that took its place
+#[derive(Debug, Clone, PartialEq)]
+pub struct Substitution {
+    pub range: Range<usize>,
+    pub literal_len: usize,
+}
+
+/// Replace every placeholder that has a literal in `literals`; also returns
+/// what was replaced, in order
+pub fn substitute(sql: &str, literals: &HashMap<String, String>) -> (String, Vec<Substitution>) {
+    let mut out = String::with_capacity(sql.len());
+    let mut done = Vec::new();
+    let mut last = 0;
+    for p in find_placeholders(sql) {
+        if let Some(lit) = literals.get(&p.name) {
+            out.push_str(&sql[last..p.range.start]);
+            out.push_str(lit);
+            last = p.range.end;
+            done.push(Substitution { range: p.range, literal_len: lit.len() });
+        }
+    }
+    out.push_str(&sql[last..]);
+    (out, done)
+}
+
+/// Move `(line, column)` in `substituted` (1-based line, 0-based column in
+/// characters) back onto `written`, the statement before `substitute`
+/// replaced `done`. Positions inside a literal land on its placeholder.
+pub fn original_position(written: &str, substituted: &str, done: &[Substitution], (line, column): (usize, usize)) -> (usize, usize) {
+    // byte offset in the substituted text
+    let line_start: usize = substituted.split('\n').take(line.saturating_sub(1)).map(|l| l.len() + 1).sum();
+    let line_start = line_start.min(substituted.len());
+    let rest = substituted[line_start..].split('\n').next().unwrap_or("");
+    let mut offset = line_start + rest.char_indices().nth(column).map(|(b, _)| b).unwrap_or(rest.len());
+
+    // how much longer the substituted text is up to `offset`
+    let mut shift = 0isize;
+    for s in done {
+        let start = s.range.start.saturating_add_signed(shift);
+        if offset < start {
+            break;
+        }
+        if offset < start + s.literal_len {
+            offset = start;
+            break;
+        }
+        shift += s.literal_len as isize - s.range.len() as isize;
+    }
+    let offset = offset.saturating_add_signed(-shift).min(written.len());
+    let offset = (0..=offset).rev().find(|&i| written.is_char_boundary(i)).unwrap_or(0);
+
+    let before = &written[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (before.matches('\n').count() + 1, before[line_start..].chars().count())
+}
@@ -0,0 +1,323 @@
+//! Binary and oversized cell values.
+//!
+//! Binary columns are shown as hex or base64 (`binary_format` in Frost.toml).
+//! Values longer than `max_cell_bytes` are streamed into a spill file that
+//! lives next to the result's tiles; the tile only keeps a reference with a
+//! short preview and the full size (a cell type of its own in the tile
+//! encoding), and the cell inspector reads the whole value back from the
+//! spill file when it is opened.
+
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+use tempfile::NamedTempFile;
+
+/// Default for `max_cell_bytes`
+pub const DEFAULT_MAX_CELL_BYTES: usize = 64 * 1024;
+
+/// Characters of an oversized text value kept in its tile
+const PREVIEW_CHARS: usize = 120;
+/// Bytes of an oversized binary value kept (encoded) in its tile
+const PREVIEW_BYTES: usize = 48;
+
+/// Starts the in-memory form of an oversized cell, `\u{FDD0}offset bytes kind>preview`,
+/// on its way between a backend and the tiles. Fetched text that happens to
+/// begin with it is stored with the mark doubled (see [`CellBuilder::finish`]),
+/// which never parses as a reference; [`unescape`] gives the real text back.
+const LARGE_MARK: char = '\u{FDD0}';
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// How binary values are turned into text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BinaryFormat {
+    /// `0x48656C6C6F`
+    #[default]
+    Hex,
+    /// `SGVsbG8=`
+    Base64,
+}
+
+impl BinaryFormat {
+    /// `"hex"` or `"base64"`, as written in Frost.toml
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "hex" => Some(BinaryFormat::Hex),
+            "base64" => Some(BinaryFormat::Base64),
+            _ => None,
+        }
+    }
+
+    pub fn encode(self, bytes: &[u8]) -> String {
+        match self {
+            BinaryFormat::Hex => {
+                let mut s = String::with_capacity(2 + bytes.len() * 2);
+                s.push_str("0x");
+                for byte in bytes {
+                    s.push_str(&format!("{:02X}", byte));
+                }
+                s
+            }
+            BinaryFormat::Base64 => {
+                let mut s = String::with_capacity(bytes.len().div_ceil(3) * 4);
+                for chunk in bytes.chunks(3) {
+                    let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+                    let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+                    for i in 0..4 {
+                        if i <= chunk.len() {
+                            s.push(BASE64_ALPHABET[(n >> (18 - 6 * i) & 0x3F) as usize] as char);
+                        } else {
+                            s.push('=');
+                        }
+                    }
+                }
+                s
+            }
+        }
+    }
+
+    fn kind(self) -> char {
+        match self {
+            BinaryFormat::Hex => 'x',
+            BinaryFormat::Base64 => 'b',
+        }
+    }
+}
+
+/// How the backends turn fetched values into cell text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValueOptions {
+    pub binary_format: BinaryFormat,
+    /// Values longer than this are spilled and only previewed in the grid
+    pub max_cell_bytes: usize,
+}
+
+impl Default for ValueOptions {
+    fn default() -> Self {
+        Self { binary_format: BinaryFormat::default(), max_cell_bytes: DEFAULT_MAX_CELL_BYTES }
+    }
+}
+
+/// An oversized cell: where its bytes are in the spill file, and its preview
+#[derive(Debug, Clone, PartialEq)]
+pub struct LargeValue<'a> {
+    pub offset: u64,
+    pub bytes: u64,
+    /// `None` for text, otherwise the format binary bytes are shown in
+    pub binary: Option<BinaryFormat>,
+    pub preview: &'a str,
+}
+
+impl<'a> LargeValue<'a> {
+    pub fn parse(cell: &'a str) -> Option<Self> {
+        let rest = cell.strip_prefix(LARGE_MARK)?;
+        let (header, preview) = rest.split_once('>')?;
+        let mut parts = header.split(' ');
+        let offset = parts.next()?.parse().ok()?;
+        let bytes = parts.next()?.parse().ok()?;
+        let binary = match parts.next()? {
+            "t" => None,
+            "x" => Some(BinaryFormat::Hex),
+            "b" => Some(BinaryFormat::Base64),
+            _ => return None,
+        };
+        Some(Self { offset, bytes, binary, preview })
+    }
+
+    /// The cell text that stands for this value
+    pub fn marker(&self) -> String {
+        let kind = self.binary.map(BinaryFormat::kind).unwrap_or('t');
+        format!("{}{} {} {}>{}", LARGE_MARK, self.offset, self.bytes, kind, self.preview)
+    }
+
+    /// What the grid shows: the preview and the full size
+    pub fn summary(&self) -> String {
+        format!("{}… [{}]", self.preview, format_size(self.bytes))
+    }
+}
+
+/// Grid text of a cell; oversized values show their preview and size
+pub fn display_text(cell: &str) -> Cow<'_, str> {
+    match LargeValue::parse(cell) {
+        Some(large) => Cow::Owned(large.summary()),
+        None => Cow::Borrowed(unescape(cell)),
+    }
+}
+
+/// The fetched text of a cell that is not an oversized value
+pub fn unescape(cell: &str) -> &str {
+    match cell.strip_prefix(LARGE_MARK) {
+        Some(rest) if rest.starts_with(LARGE_MARK) => rest,
+        _ => cell,
+    }
+}
+
+/// `512 B`, `1.2 KB`, `34.0 MB`
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// Where spilled bytes live: a temp file while fetching, or the spill
+/// section of a saved `.frostres` file
+#[derive(Debug)]
+struct SpillFile {
+    file: File,
+    /// Start of the values in `file`; marker offsets are relative to it
+    base: u64,
+    len: u64,
+    /// Owns the temp file while fetching; `None` for saved files, which are read-only
+    temp: Option<NamedTempFile>,
+}
+
+/// Append-only file with the full bytes of a result's oversized cells.
+/// The temp file is only created once the first value spills.
+#[derive(Debug, Default)]
+pub struct ValueSpill {
+    file: Mutex<Option<SpillFile>>,
+}
+
+impl ValueSpill {
+    /// The `len` bytes at `base` of `path`, written earlier by [`write_to`](Self::write_to)
+    pub fn open_saved(path: &Path, base: u64, len: u64) -> io::Result<Self> {
+        let file = File::open(path)?;
+        Ok(Self { file: Mutex::new(Some(SpillFile { file, base, len, temp: None })) })
+    }
+
+    /// Append `bytes` and return the offset they start at
+    fn append(&self, bytes: &[u8]) -> io::Result<u64> {
+        let mut guard = self.file.lock().unwrap();
+        if guard.is_none() {
+            let temp = NamedTempFile::new()?;
+            let file = temp.reopen()?;
+            *guard = Some(SpillFile { file, base: 0, len: 0, temp: Some(temp) });
+        }
+        let spill = guard.as_mut().unwrap();
+        if spill.temp.is_none() {
+            return Err(io::Error::new(io::ErrorKind::PermissionDenied, "Saved results are read-only"));
+        }
+        let offset = spill.len;
+        spill.file.seek(SeekFrom::Start(spill.base + offset))?;
+        spill.file.write_all(bytes)?;
+        spill.len += bytes.len() as u64;
+        Ok(offset)
+    }
+
+    fn read(&self, offset: u64, bytes: u64) -> io::Result<Vec<u8>> {
+        let mut guard = self.file.lock().unwrap();
+        let spill = guard
+            .as_mut()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No spilled values"))?;
+        if offset.checked_add(bytes).map_or(true, |end| end > spill.len) {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "Spilled value is out of range"));
+        }
+        spill.file.seek(SeekFrom::Start(spill.base + offset))?;
+        let mut buf = vec![0; bytes as usize];
+        spill.file.read_exact(&mut buf)?;
+        Ok(buf)
+    }
+
+    /// Copy every spilled byte to `out`; returns how many were written
+    pub fn write_to(&self, out: &mut impl Write) -> io::Result<u64> {
+        let mut guard = self.file.lock().unwrap();
+        let Some(spill) = guard.as_mut() else { return Ok(0) };
+        spill.file.seek(SeekFrom::Start(spill.base))?;
+        let copied = io::copy(&mut (&mut spill.file).take(spill.len), out)?;
+        if copied != spill.len {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "Spill file is shorter than expected"));
+        }
+        Ok(copied)
+    }
+
+    /// The complete text of `cell`, reading oversized values back in
+    pub fn full_value(&self, cell: &str) -> io::Result<String> {
+        match LargeValue::parse(cell) {
+            Some(large) => {
+                let bytes = self.read(large.offset, large.bytes)?;
+                Ok(match large.binary {
+                    Some(format) => format.encode(&bytes),
+                    None => String::from_utf8_lossy(&bytes).into_owned(),
+                })
+            }
+            None => Ok(unescape(cell).to_string()),
+        }
+    }
+}
+
+/// Builds cell text from bytes that arrive in chunks. Nothing past
+/// `max_cell_bytes` is kept in memory; the rest goes straight to the spill.
+pub struct CellBuilder<'a> {
+    options: ValueOptions,
+    spill: &'a ValueSpill,
+    binary: bool,
+    buf: Vec<u8>,
+    /// Offset and length once the value has spilled
+    spilled: Option<(u64, u64)>,
+}
+
+impl<'a> CellBuilder<'a> {
+    pub fn new(options: ValueOptions, spill: &'a ValueSpill, binary: bool) -> Self {
+        Self { options, spill, binary, buf: Vec::new(), spilled: None }
+    }
+
+    pub fn push(&mut self, chunk: &[u8]) -> io::Result<()> {
+        if let Some((_, len)) = &mut self.spilled {
+            self.spill.append(chunk)?;
+            *len += chunk.len() as u64;
+            return Ok(());
+        }
+        self.buf.extend_from_slice(chunk);
+        if self.buf.len() > self.options.max_cell_bytes {
+            let offset = self.spill.append(&self.buf)?;
+            self.spilled = Some((offset, self.buf.len() as u64));
+            let keep = if self.binary { PREVIEW_BYTES } else { PREVIEW_CHARS * 4 };
+            self.buf.truncate(keep);
+        }
+        Ok(())
+    }
+
+    pub fn finish(self) -> String {
+        let format = self.binary.then_some(self.options.binary_format);
+        let Some((offset, bytes)) = self.spilled else {
+            return match format {
+                Some(format) => format.encode(&self.buf),
+                None => {
+                    let text = String::from_utf8_lossy(&self.buf);
+                    if text.starts_with(LARGE_MARK) {
+                        format!("{}{}", LARGE_MARK, text)
+                    } else {
+                        text.into_owned()
+                    }
+                }
+            };
+        };
+        let preview = match format {
+            Some(format) => format.encode(&self.buf),
+            None => {
+                // the cut may have split a character; drop its remains
+                let text: String = String::from_utf8_lossy(&self.buf).chars().take(PREVIEW_CHARS).collect();
+                text.trim_end_matches('\u{FFFD}').to_string()
+            }
+        };
+        LargeValue { offset, bytes, binary: format, preview: &preview }.marker()
+    }
+}
+
+/// A whole value at once, e.g. from a backend that can't stream
+pub fn build_cell(options: ValueOptions, spill: &ValueSpill, binary: bool, bytes: &[u8]) -> io::Result<String> {
+    let mut builder = CellBuilder::new(options, spill, binary);
+    builder.push(bytes)?;
+    Ok(builder.finish())
+}
@@ -0,0 +1,140 @@
+//! Logical column types for result sets.
+//!
+//! Backends map their native SQL types onto [`ColumnType`]; the tile store
+//! records it per column and encodes cells accordingly, and the grid,
+//! selection stats and exports use it instead of guessing.
+
+use chrono::{NaiveDate, NaiveDateTime};
+
+use crate::tile_rowstore::NULL_SENTINEL;
+
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+pub const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColumnType {
+    Text,
+    Integer,
+    Decimal,
+    Float,
+    Boolean,
+    Date,
+    Time,
+    Timestamp,
+    Binary,
+}
+
+impl ColumnType {
+    /// Tag byte used in the tile file header
+    pub fn tag(self) -> u8 {
+        match self {
+            ColumnType::Text => 0,
+            ColumnType::Integer => 1,
+            ColumnType::Decimal => 2,
+            ColumnType::Float => 3,
+            ColumnType::Boolean => 4,
+            ColumnType::Date => 5,
+            ColumnType::Time => 6,
+            ColumnType::Timestamp => 7,
+            ColumnType::Binary => 8,
+        }
+    }
+
+    /// Inverse of [`tag`](Self::tag); `None` for a byte no version wrote
+    pub fn from_tag(tag: u8) -> Option<Self> {
+        Some(match tag {
+            0 => ColumnType::Text,
+            1 => ColumnType::Integer,
+            2 => ColumnType::Decimal,
+            3 => ColumnType::Float,
+            4 => ColumnType::Boolean,
+            5 => ColumnType::Date,
+            6 => ColumnType::Time,
+            7 => ColumnType::Timestamp,
+            8 => ColumnType::Binary,
+            _ => return None,
+        })
+    }
+
+    pub fn is_numeric(self) -> bool {
+        matches!(self, ColumnType::Integer | ColumnType::Decimal | ColumnType::Float)
+    }
+
+    /// Map a declared SQL type name (`NUMBER(10,0)`, `VARCHAR`, `TIMESTAMP_NTZ`, …)
+    pub fn from_type_name(name: &str) -> Self {
+        let upper = name.trim().to_uppercase();
+        let base = upper.split('(').next().unwrap_or("").trim();
+        let scale_zero = upper
+            .split(',')
+            .nth(1)
+            .map(|s| s.trim_end_matches(')').trim() == "0")
+            .unwrap_or(true);
+
+        match base {
+            "INT" | "INTEGER" | "BIGINT" | "SMALLINT" | "TINYINT" | "BYTEINT" | "MEDIUMINT" => ColumnType::Integer,
+            "NUMBER" | "NUMERIC" | "DECIMAL" if scale_zero => ColumnType::Integer,
+            "NUMBER" | "NUMERIC" | "DECIMAL" => ColumnType::Decimal,
+            "FLOAT" | "FLOAT4" | "FLOAT8" | "DOUBLE" | "DOUBLE PRECISION" | "REAL" => ColumnType::Float,
+            "BOOLEAN" | "BOOL" | "BIT" => ColumnType::Boolean,
+            "DATE" => ColumnType::Date,
+            "TIME" => ColumnType::Time,
+            b if b.starts_with("TIMESTAMP") || b == "DATETIME" => ColumnType::Timestamp,
+            "BINARY" | "VARBINARY" | "BLOB" => ColumnType::Binary,
+            _ => ColumnType::Text,
+        }
+    }
+
+    /// Interpret a cell of this column; falls back to `Text` if it doesn't parse.
+    pub fn parse(self, cell: &str) -> TypedValue {
+        if cell == NULL_SENTINEL {
+            return TypedValue::Null;
+        }
+        let trimmed = cell.trim();
+        let parsed = match self {
+            ColumnType::Integer => trimmed.parse::<i64>().ok().map(TypedValue::Int)
+                .or_else(|| trimmed.parse::<f64>().ok().map(TypedValue::Num)),
+            ColumnType::Decimal | ColumnType::Float => trimmed.parse::<f64>().ok().map(TypedValue::Num),
+            ColumnType::Boolean => parse_bool(trimmed).map(TypedValue::Bool),
+            ColumnType::Date => NaiveDate::parse_from_str(trimmed, DATE_FORMAT).ok().map(TypedValue::Date),
+            ColumnType::Timestamp => parse_timestamp(trimmed).map(TypedValue::Timestamp),
+            ColumnType::Text | ColumnType::Time | ColumnType::Binary => None,
+        };
+        parsed.unwrap_or_else(|| TypedValue::Text(cell.to_string()))
+    }
+}
+
+/// A cell interpreted through its column type
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypedValue {
+    Null,
+    Int(i64),
+    Num(f64),
+    Bool(bool),
+    Date(NaiveDate),
+    Timestamp(NaiveDateTime),
+    Text(String),
+}
+
+impl TypedValue {
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            TypedValue::Int(i) => Some(*i as f64),
+            TypedValue::Num(n) => Some(*n),
+            _ => None,
+        }
+    }
+}
+
+pub fn parse_bool(s: &str) -> Option<bool> {
+    match s {
+        "1" | "true" | "TRUE" | "True" => Some(true),
+        "0" | "false" | "FALSE" | "False" => Some(false),
+        _ => None,
+    }
+}
+
+pub fn parse_timestamp(s: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f")
+        .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f"))
+        .ok()
+}
@@ -1,17 +1,122 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::fs;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Config {
-    /// Snowflake connection string
+    /// Snowflake ODBC connection string, or `sqlite:///path` / `sqlite::memory:`
     pub connection_string: String,
+
+    /// Profile to connect with on startup (top-level `connection_string` if unset)
+    pub default_profile: Option<String>,
+
+    /// Named connections from `[profiles.<name>]`
+    pub profiles: BTreeMap<String, Profile>,
+
+    /// Stop fetching a result set after this many rows (0 or unset: no cap)
+    pub fetch_limit: Option<usize>,
+
+    /// Cancel statements that run longer than this many seconds (0 or unset: never)
+    pub statement_timeout: Option<u64>,
+
+    /// Connections a parallel run (F4) may use, the main one included (default 4)
+    pub parallel_connections: Option<usize>,
+
+    /// Keep running a script's later statements after one fails (F12 toggles)
+    pub continue_on_error: Option<bool>,
+
+    /// How binary columns are shown: "hex" (default) or "base64"
+    pub binary_format: Option<String>,
+
+    /// Cells longer than this many bytes are kept on disk and previewed (default 65536)
+    pub max_cell_bytes: Option<usize>,
+
+    /// Megabytes of decoded result tiles kept in memory across all result tabs (default 64)
+    pub tile_cache_mb: Option<usize>,
     
     /// Theme colors (all RGB values)
     pub colors: ColorConfig,
 }
 
+/// One `[profiles.<name>]` entry
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    pub connection_string: String,
+    pub role: Option<String>,
+    pub warehouse: Option<String>,
+    pub database: Option<String>,
+    /// Status bar badge colour, RGB
+    pub accent: Option<[u8; 3]>,
+}
+
+impl Profile {
+    /// Connection string with the profile's role/warehouse/database applied
+    /// (they replace any `Role=`/`Warehouse=`/`Database=` already present).
+    pub fn effective_connection_string(&self) -> String {
+        if self.connection_string.trim_start().to_ascii_lowercase().starts_with("sqlite:") {
+            return self.connection_string.clone();
+        }
+
+        let overrides: Vec<(&str, &str)> = [
+            ("Role", &self.role),
+            ("Warehouse", &self.warehouse),
+            ("Database", &self.database),
+        ]
+        .into_iter()
+        .filter_map(|(key, val)| val.as_deref().map(|v| (key, v)))
+        .collect();
+
+        if overrides.is_empty() {
+            return self.connection_string.clone();
+        }
+
+        let mut parts: Vec<String> = split_attributes(&self.connection_string)
+            .into_iter()
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+            .filter(|part| {
+                let key = part.split('=').next().unwrap_or("").trim();
+                !overrides.iter().any(|(k, _)| key.eq_ignore_ascii_case(k))
+            })
+            .map(str::to_string)
+            .collect();
+        parts.extend(overrides.iter().map(|(k, v)| format!("{}={}", k, v)));
+
+        format!("{};", parts.join(";"))
+    }
+}
+
+/// Split an ODBC connection string at the `;` between attributes, keeping
+/// braced values like `PWD={a;b}` (with `}}` for a literal `}`) whole
+fn split_attributes(conn_str: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut in_braces = false;
+    let mut chars = conn_str.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '{' if !in_braces => in_braces = true,
+            '}' if in_braces => {
+                if chars.peek().is_some_and(|&(_, next)| next == '}') {
+                    chars.next();
+                } else {
+                    in_braces = false;
+                }
+            }
+            ';' if !in_braces => {
+                parts.push(&conn_str[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&conn_str[start..]);
+    parts
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ColorConfig {
     // Editor colors
@@ -79,6 +184,15 @@ impl Default for Config {
                 Database=your_database;\
                 Schema=your_schema;"
             ),
+            default_profile: None,
+            profiles: BTreeMap::new(),
+            fetch_limit: None,
+            statement_timeout: None,
+            parallel_connections: None,
+            continue_on_error: None,
+            binary_format: None,
+            max_cell_bytes: None,
+            tile_cache_mb: None,
             colors: ColorConfig::default(),
         }
     }
@@ -157,6 +271,22 @@ impl Config {
         let config: Config = toml::from_str(&contents)?;
         Ok(config)
     }
+
+    /// Resolve the profile to connect with: `name`, else `default_profile`,
+    /// else the top-level `connection_string` (returned with no name).
+    pub fn profile(&self, name: Option<&str>) -> anyhow::Result<(Option<String>, Profile)> {
+        match name.or(self.default_profile.as_deref()) {
+            Some(name) => {
+                let profile = self.profiles.get(name)
+                    .ok_or_else(|| anyhow::anyhow!("Unknown profile '{}' in Frost.toml", name))?;
+                Ok((Some(name.to_string()), profile.clone()))
+            }
+            None => Ok((None, Profile {
+                connection_string: self.connection_string.clone(),
+                ..Profile::default()
+            })),
+        }
+    }
     
     fn config_path() -> anyhow::Result<PathBuf> {
         let exe_path = std::env::current_exe()?;
@@ -170,6 +300,7 @@ impl Config {
 # Place this file in the same directory as the Frost executable
 
 # Snowflake connection string
+# (use "sqlite:///path/to/file.db" or "sqlite::memory:" to work against a local SQLite database)
 connection_string = """
 Driver=SnowflakeDSIIDriver;
 Server=your-account.snowflakecomputing.com;
@@ -181,6 +312,61 @@ Database=your_database;
 Schema=your_schema;
 """
 
+# Secrets don't have to be written out here: ${env:SNOWFLAKE_PWD} reads an
+# environment variable, ${file:~/.secrets/sf_key} a file, and ${prompt:Password}
+# asks when Frost connects (batch mode reads FROST_PROMPT_PASSWORD or stdin), e.g.
+#   PWD=${prompt:Password};
+
+# Stop fetching after this many rows per statement; press n / N in the results
+# pane to fetch the next chunk / the rest. Override per statement with a
+# "--@limit 5000" comment line above it ("--@limit 0" for no cap). 0 disables
+# the cap.
+fetch_limit = 100000
+
+# Cancel statements that run longer than this many seconds (0 = no timeout).
+# Override per statement with a "--@timeout 30s" / "--@timeout 10m" comment
+# line above it;
+# batch mode also takes --statement-timeout <secs>.
+statement_timeout = 0
+
+# F4 runs the selected statements side by side on up to this many connections
+# (the main one included). The extra sessions are opened on first use and
+# start from the connection string's defaults, not from USE statements run here.
+parallel_connections = 4
+
+# Keep running the rest of a multi-statement run after a statement fails; the
+# failures become error tabs. F12 toggles it in the app.
+continue_on_error = false
+
+# Binary columns are shown as "hex" (0x48656C6C6F) or "base64" (SGVsbG8=).
+binary_format = "hex"
+
+# Values longer than this many bytes are fetched in chunks and kept on disk;
+# the grid shows a preview and the size, and v in the results pane opens the
+# whole value. Exports and copies always get the full value.
+max_cell_bytes = 65536
+
+# Megabytes of decoded result rows kept in memory, shared by all result tabs; the
+# tabs you look at most recently keep theirs. Tiles
+# around the visible rows are decoded in the background, further ahead in the
+# direction you scroll; raise this for smoother scrolling through wide results.
+tile_cache_mb = 64
+
+# Named connection profiles - switch in the app with F2, or start with --profile <name>
+# default_profile = "dev"
+#
+# [profiles.dev]
+# connection_string = """
+# Driver=SnowflakeDSIIDriver;
+# Server=your-account.snowflakecomputing.com;
+# UID=your-email@example.com;
+# Authenticator=externalbrowser;
+# """
+# role = "DEV_ROLE"                     # Optional default role/warehouse/database
+# warehouse = "DEV_WH"
+# database = "DEV_DB"
+# accent = [106, 149, 137]              # Status bar badge colour
+
 # Theme colors - all values are RGB arrays [red, green, blue]
 # You can customize any of these colors to your preference
 
@@ -7,22 +7,48 @@ use crate::schema_cache::{SchemaCache, ObjectType};
 
 pub struct DbNavigator {
     data_dir: PathBuf,
+    profile: Option<String>,
     cache: Option<SchemaCache>,
 }
 
+/// A profile name made safe to use as one directory name: path separators
+/// and other odd characters become `_`, and `.`/`..` can't climb out
+fn profile_dir_name(name: &str) -> String {
+    let safe: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | ' ') { c } else { '_' })
+        .collect();
+    if safe.trim_matches('.').is_empty() {
+        "_".repeat(safe.len().max(1))
+    } else {
+        safe
+    }
+}
+
 impl DbNavigator {
     pub fn new() -> Self {
+        Self::for_profile(None)
+    }
+
+    /// Named profiles keep their cache and crawler queue in `profiles/<name>/`
+    pub fn for_profile(profile: Option<&str>) -> Self {
         // Use the same approach as config - everything in the executable directory
-        let data_dir = std::env::current_exe()
+        let mut data_dir = std::env::current_exe()
             .ok()
             .and_then(|exe| exe.parent().map(|p| p.to_path_buf()))
             .and_then(|p| std::fs::canonicalize(p).ok())
             .unwrap_or_else(|| PathBuf::from("."));
+
+        if let Some(name) = profile {
+            data_dir = data_dir.join("profiles").join(profile_dir_name(name));
+            let _ = fs::create_dir_all(&data_dir);
+        }
         
         //eprintln!("DbNavigator using data_dir: {}", data_dir.display());
         
         Self {
             data_dir,
+            profile: profile.map(str::to_string),
             cache: None,
         }
     }
@@ -89,10 +115,14 @@ impl DbNavigator {
         //eprintln!("Launching crawler: {}", crawler_exe.display());
         //eprintln!("With --data-dir: {}", self.data_dir.display());
         
-        let _ = Command::new(&crawler_exe)
-            .arg("--data-dir")
-            .arg(&self.data_dir)
-            .spawn();
+        let mut cmd = Command::new(&crawler_exe);
+        cmd.arg("--data-dir").arg(&self.data_dir);
+        if let Some(profile) = &self.profile {
+            cmd.arg("--profile").arg(profile);
+        }
+        // values typed in for ${prompt:...} placeholders
+        cmd.envs(crate::secrets::prompt_env());
+        let _ = cmd.spawn();
 
         //eprintln!("Crawler launch attempted");
     }
@@ -59,6 +59,9 @@ pub struct DbTree {
     pending_action: Option<TreeAction>,
     connected: bool,
     needs_refresh: bool, 
+    // Session's current database and schema (marked, and left off inserted names)
+    current_database: Option<String>,
+    current_schema: Option<String>,
 }
 
 impl DbTree {
@@ -82,6 +85,8 @@ impl DbTree {
             pending_action: None,
             connected: false,
             needs_refresh: true,
+            current_database: None,
+            current_schema: None,
         };
 
         
@@ -109,14 +114,47 @@ impl DbTree {
         self.connected = connected;
     }
 
+    /// Point the navigator at another profile's schema cache
+    pub fn set_profile(&mut self, profile: Option<&str>) {
+        self.db_navigator = crate::db_navigator::DbNavigator::for_profile(profile);
+        self.cache = Some(SchemaCache::new());
+        self.expanded.clear();
+        self.selected_index = 0;
+        self.view_offset = 0;
+        self.selected_role_index = 0;
+        self.role_selection_mode = false;
+        self.connected = false;
+        self.needs_refresh = true;
+        self.rebuild_visible_nodes();
+    }
+
+    /// Track the session's database and schema
+    pub fn set_session_context(&mut self, context: Option<crate::query_backend::SessionContext>) {
+        let context = context.unwrap_or_default();
+        self.current_database = context.database;
+        self.current_schema = context.schema;
+    }
+
+    /// Object name as the session resolves it: the database and schema are
+    /// left off when they are the current ones
+    fn qualified_name(&self, db: &str, schema: &str, obj: &str) -> String {
+        if self.current_database.as_deref() != Some(db) {
+            format!("{}.{}.{}", db, schema, obj)
+        } else if self.current_schema.as_deref() != Some(schema) {
+            format!("{}.{}", schema, obj)
+        } else {
+            obj.to_string()
+        }
+    }
+
     fn get_object_text(&self, node: &TreeNode) -> Option<String> {
         match node {
             TreeNode::Object(db, schema, table, ObjectType::Table) |
             TreeNode::Object(db, schema, table, ObjectType::View) => {
-                Some(format!("{}.{}.{}\n", db, schema, table))
+                Some(format!("{}\n", self.qualified_name(db, schema, table)))
             }
             TreeNode::Object(db, schema, obj, _) => {
-                Some(format!("{}.{}.{}\n", db, schema, obj))
+                Some(format!("{}\n", self.qualified_name(db, schema, obj)))
             }
             TreeNode::Column(_, _, _, column, _) => {
                 Some(format!("{}\n", column))
@@ -968,13 +1006,17 @@ impl DbTree {
                     TreeNode::Database(name) => {
                         let expanded = self.expanded.contains(name);
                         let prefix = if expanded { "▼ " } else { "▶ " };
-                        (prefix, format!("󰆼 {}", name), STYLE::info_fg())
+                        let current = if self.current_database.as_ref() == Some(name) { " ●" } else { "" };
+                        (prefix, format!("󰆼 {}{}", name, current), STYLE::info_fg())
                     }
-                    TreeNode::Schema(_, name) => {
+                    TreeNode::Schema(db, name) => {
                         let key = Self::get_node_key(node);
                         let expanded = self.expanded.contains(&key);
                         let prefix = if expanded { "▼ " } else { "▶ " };
-                        (prefix, format!("󰙅 {}", name), STYLE::status_fg())
+                        let current = self.current_database.as_ref() == Some(db)
+                            && self.current_schema.as_ref() == Some(name);
+                        let current = if current { " ●" } else { "" };
+                        (prefix, format!("󰙅 {}{}", name, current), STYLE::status_fg())
                     }
                     TreeNode::ObjectType(_, _, obj_type) => {
                         let expanded = self.expanded.contains(&Self::get_node_key(node));
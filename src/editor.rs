@@ -173,6 +173,8 @@ pub struct Editor {
     pub dirty: bool,
     pub autocomplete: crate::autocomplete::Autocomplete,
     pub schema_cache: Option<crate::schema_cache::SchemaCache>,
+    /// Current database / schema of the session, for unqualified names
+    pub session_context: Option<crate::query_backend::SessionContext>,
     last_autocomplete_update: Option<Instant>,
     autocomplete_pending: bool,
     drag_anchor: Option<usize>,
@@ -241,6 +243,7 @@ impl Editor {
             bracket_match: None,
             autocomplete: crate::autocomplete::Autocomplete::new(),
             schema_cache: None,
+            session_context: None,
         }
     }
 
@@ -903,7 +906,7 @@ impl Editor {
             }
             (KeyCode::Char(' '), KeyModifiers::CONTROL) => {
                 // Manually trigger autocomplete
-                self.autocomplete.update_suggestions(&self.buffer, self.caret, self.schema_cache.as_ref());
+                self.autocomplete.update_suggestions(&self.buffer, self.caret, self.schema_cache.as_ref(), self.session_context.as_ref());
                 self.preferred_col = cur_col;
             }
             (KeyCode::Char('v') | KeyCode::Char('V'), KeyModifiers::CONTROL) => {
@@ -974,12 +977,12 @@ impl Editor {
                     self.autocomplete_pending = true;
                     if let Some(last) = self.last_autocomplete_update {
                         if last.elapsed() > Duration::from_millis(150) {
-                            self.autocomplete.update_suggestions(&self.buffer, self.caret, self.schema_cache.as_ref());
+                            self.autocomplete.update_suggestions(&self.buffer, self.caret, self.schema_cache.as_ref(), self.session_context.as_ref());
                             self.last_autocomplete_update = Some(Instant::now());
                             self.autocomplete_pending = false;
                         }
                     } else {
-                        self.autocomplete.update_suggestions(&self.buffer, self.caret, self.schema_cache.as_ref());
+                        self.autocomplete.update_suggestions(&self.buffer, self.caret, self.schema_cache.as_ref(), self.session_context.as_ref());
                         self.last_autocomplete_update = Some(Instant::now());
                     }
                 }
@@ -1762,6 +1765,20 @@ impl Editor {
         self.bracket_match = self.find_bracket_match(self.caret);
     }
     
+    /// Put the caret at byte `offset` (e.g. an error position) and scroll to it
+    pub fn move_caret_to(&mut self, offset: usize) {
+        let mut offset = offset.min(self.buffer.len());
+        while !self.buffer.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        self.caret = offset;
+        self.selection = None;
+        self.last_action = None;
+        self.preferred_col = self.caret_line_col().1;
+        self.nudge_view_to_caret();
+        self.update_bracket_match();
+    }
+
     pub fn caret_line_col(&self) -> (usize, usize) {
         let mut idx = 0;
         let mut line = 0;
@@ -4,6 +4,7 @@
 pub mod config;
 pub mod db_navigator;
 pub mod schema_cache;
+pub mod secrets;
 
 pub use schema_cache::{
     SchemaCache, Database, Schema, SchemaObject, Column, ObjectType, DataType,
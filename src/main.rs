@@ -5,6 +5,12 @@ mod results_selection;
 mod results_export;
 mod workspace;
 mod tile_rowstore;
+mod column_type;
+mod bind_vars;
+mod query_history;
+mod sql_error;
+mod cell_value;
+mod result_file;
 mod syntax;
 mod palette;
 mod autocomplete;
@@ -12,8 +18,11 @@ mod config;
 mod batch_mode;
 mod batch_generator;
 mod schema_cache;
+mod secrets;
 mod db_tree;
 mod db_navigator;
+mod query_backend;
+mod sqlite_backend;
 
 use std::path::PathBuf;
 use std::process;
@@ -38,7 +47,9 @@ use windows_sys::Win32::{
 };
 
 /* ─── modules / crates ─── */
-use crate::workspace::Workspace;
+use crate::workspace::{WorkerOptions, Workspace, DEFAULT_PARALLEL_CONNECTIONS};
+use crate::query_backend::backend_for;
+use crate::cell_value::{BinaryFormat, ValueOptions, DEFAULT_MAX_CELL_BYTES};
 use crossterm::event::Event;
 use tui::{backend::CrosstermBackend, Terminal};
 
@@ -64,6 +75,14 @@ struct Cli {
     /// SQL file to open (interactive mode)
     #[arg(value_name = "FILE")]
     file: Option<PathBuf>,
+
+    /// Connection profile from Frost.toml ([profiles.<name>])
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
+    /// Saved result (.frostres) to open in a results tab
+    #[arg(long, value_name = "FILE")]
+    open_result: Option<PathBuf>,
     
     #[command(subcommand)]
     command: Option<Commands>,
@@ -96,6 +115,11 @@ enum Commands {
         /// Save only the last query result
         #[arg(short = 'l', long)]
         last_query_only: bool,
+
+        /// Cancel statements running longer than this many seconds
+        /// (defaults to statement_timeout in Frost.toml; 0 disables)
+        #[arg(long, value_name = "SECS")]
+        statement_timeout: Option<u64>,
     },
 }
 
@@ -144,6 +168,19 @@ fn set_console_title(title: &str) {
     unsafe { SetConsoleTitleW(wide.as_ptr()) };
 }
 
+/// `binary_format` and `max_cell_bytes` from Frost.toml
+fn value_options(config: &crate::config::Config) -> ValueOptions {
+    ValueOptions {
+        binary_format: config.binary_format
+            .as_deref()
+            .and_then(BinaryFormat::parse)
+            .unwrap_or_default(),
+        max_cell_bytes: config.max_cell_bytes
+            .filter(|&n| n > 0)
+            .unwrap_or(DEFAULT_MAX_CELL_BYTES),
+    }
+}
+
 /*──────────────────────── main ────────────────────────────────*/
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
@@ -152,7 +189,7 @@ fn main() -> anyhow::Result<()> {
     let config_result = crate::config::Config::load();
     
     // Handle batch mode
-    if let Some(Commands::Batch { sql_file, output_dir, format, exit_on_error, verbose, last_query_only }) = cli.command {
+    if let Some(Commands::Batch { sql_file, output_dir, format, exit_on_error, verbose, last_query_only, statement_timeout }) = cli.command {
         let config = config_result?; // For batch mode, we need valid config
         let output_format = match format.as_str() {
             "json" => OutputFormat::Json,
@@ -165,13 +202,20 @@ fn main() -> anyhow::Result<()> {
             sql_file,
             output_dir,
             output_format,
-            connection_string: config.connection_string,
             exit_on_error,
             verbose,
             last_query_only,
+            statement_timeout: statement_timeout
+                .or(config.statement_timeout)
+                .filter(|&secs| secs > 0)
+                .map(std::time::Duration::from_secs),
+            values: value_options(&config),
         };
         
-        return batch_mode::run_batch_mode(batch_config);
+        let (_, profile) = config.profile(cli.profile.as_deref())?;
+        let conn_str = profile.effective_connection_string();
+        batch_mode::read_secret_prompts(&conn_str)?;
+        return batch_mode::run_batch_mode(batch_config, backend_for(&conn_str));
     }
     
     // Interactive mode - handle config error gracefully
@@ -181,16 +225,25 @@ fn main() -> anyhow::Result<()> {
             // Create a default config with empty connection string
             let default_config = crate::config::Config {
                 connection_string: String::new(),
+                default_profile: None,
+                profiles: Default::default(),
+                fetch_limit: None,
+                statement_timeout: None,
+                parallel_connections: None,
+                continue_on_error: None,
+                binary_format: None,
+                max_cell_bytes: None,
+                tile_cache_mb: None,
                 colors: crate::config::ColorConfig::default(),
             };
             (default_config, Some(e.to_string()))
         }
     };
     
-    run_interactive_mode(config, cli.file, config_error)
+    run_interactive_mode(config, cli.file, cli.open_result, cli.profile, config_error)
 }
 
-fn run_interactive_mode(config: crate::config::Config, file_arg: Option<PathBuf>, config_error: Option<String>) -> anyhow::Result<()> {
+fn run_interactive_mode(config: crate::config::Config, file_arg: Option<PathBuf>, result_arg: Option<PathBuf>, profile_arg: Option<String>, config_error: Option<String>) -> anyhow::Result<()> {
     /* ①  pick a monospace font */
     const CONSOLAS_U16: [u16; 9] =
         [0x0043, 0x006f, 0x006e, 0x0073, 0x006f, 0x006c, 0x0061, 0x0073, 0];
@@ -201,9 +254,35 @@ fn run_interactive_mode(config: crate::config::Config, file_arg: Option<PathBuf>
     let _guard = palette::apply_palette()?; // keep guard alive
 
     /* ③  Workspace + optional file load */
-    let mut workspace = Workspace::new(config.connection_string)?;
+    // An unknown profile falls back to the top-level connection string
+    let (profile_name, profile, profile_error) = match config.profile(profile_arg.as_deref()) {
+        Ok((name, profile)) => (name, profile, None),
+        Err(e) => {
+            let (name, profile) = config.profile(None).unwrap_or_default();
+            (name, profile, Some(e.to_string()))
+        }
+    };
+    let worker_options = WorkerOptions {
+        fetch_limit: config.fetch_limit.filter(|&n| n > 0),
+        statement_timeout: config.statement_timeout
+            .filter(|&secs| secs > 0)
+            .map(std::time::Duration::from_secs),
+        parallel_connections: config.parallel_connections
+            .filter(|&n| n > 0)
+            .unwrap_or(DEFAULT_PARALLEL_CONNECTIONS),
+        values: value_options(&config),
+    };
+    let cache_mb = config.tile_cache_mb
+        .filter(|&mb| mb > 0)
+        .unwrap_or(tile_rowstore::DEFAULT_CACHE_MB);
+    tile_rowstore::set_cache_budget(cache_mb * 1024 * 1024);
+    let conn_str = profile.effective_connection_string();
+    let mut workspace = Workspace::new(backend_for(&conn_str), worker_options)?;
+    workspace.set_profiles(config.profiles.clone(), profile_name);
+    workspace.set_connection_string(conn_str);
+    workspace.continue_on_error = config.continue_on_error.unwrap_or(false);
     // Set initial status message if config had an error
-    if let Some(error_msg) = config_error {
+    if let Some(error_msg) = config_error.or(profile_error) {
         workspace.status_message = Some(error_msg);
         workspace.status_message_time = Some(Instant::now());
     }
@@ -244,6 +323,13 @@ fn run_interactive_mode(config: crate::config::Config, file_arg: Option<PathBuf>
     } else {
         set_console_title("Frost");
     }
+    if let Some(path) = result_arg {
+        let msg = match workspace.open_result_file(&path) {
+            Ok(m) | Err(m) => m,
+        };
+        workspace.status_message = Some(msg);
+        workspace.status_message_time = Some(Instant::now());
+    }
 
     /* ④  Crossterm / TUI init */
     crossterm::terminal::enable_raw_mode()?;
@@ -283,6 +369,9 @@ fn run_interactive_mode(config: crate::config::Config, file_arg: Option<PathBuf>
         if workspace.poll_db_responses() {
             dirty = true;
         }
+        if workspace.exit_ready {
+            break 'main;
+        }
 
         let timeout = if workspace.running { timer_update } else { anim_tick }
             .saturating_sub(last_anim.elapsed());
@@ -0,0 +1,625 @@
+//! Pluggable query engines behind the DB worker.
+//!
+//! `start_db_worker` and batch mode only talk to a [`QueryBackend`]; the
+//! ODBC implementation below is what Frost uses against Snowflake.
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use odbc::{create_environment_v3, Connection, Data, Environment, GetDiagRec, Handle, NoData, Statement, Version3};
+use odbc::ffi::{
+    SQLCancel, SQLGetData, SQLMoreResults, SqlCDataType, SqlDataType, SQLHSTMT, SQLLEN, SQLPOINTER, SQLRETURN,
+    SQL_NO_TOTAL, SQL_NULL_DATA,
+};   // raw FFI symbols live in `odbc::ffi`
+use odbc::odbc_safe::{AutocommitMode, AutocommitOff, AutocommitOn};
+use once_cell::sync::Lazy;
+
+use crate::cell_value::{CellBuilder, ValueOptions, ValueSpill};
+use crate::column_type::ColumnType;
+use crate::sqlite_backend::{SqliteBackend, SQLITE_PREFIX};
+
+/// Error reported by a backend, already formatted for display.
+#[derive(Debug, Clone)]
+pub struct BackendError {
+    pub message: String,
+    /// Server-side id of the failed statement, when the backend has one
+    pub query_id: Option<String>,
+}
+
+impl BackendError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self { message: message.into(), query_id: None }
+    }
+
+    /// SQLSTATE of an ODBC-style `State: XXXXX, Native error: …` message
+    pub fn sqlstate(&self) -> Option<&str> {
+        let rest = self.message.split("State: ").nth(1)?;
+        rest.get(..5)
+    }
+
+    /// Class 08 means the connection itself failed, not the statement
+    pub fn is_connection_lost(&self) -> bool {
+        self.sqlstate().is_some_and(|state| state.starts_with("08"))
+    }
+}
+
+impl fmt::Display for BackendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for BackendError {}
+
+pub type BackendResult<T> = Result<T, BackendError>;
+
+/// Description of one result column.
+#[derive(Debug, Clone)]
+pub struct ColumnInfo {
+    pub name: String,
+    pub col_type: ColumnType,
+}
+
+/// Rows of a result set, pulled one at a time. `None` cells are SQL NULLs.
+pub trait RowStream {
+    fn columns(&self) -> &[ColumnInfo];
+    fn next_row(&mut self) -> BackendResult<Option<Vec<Option<String>>>>;
+
+    /// Server-side id of the statement that produced the rows
+    fn query_id(&self) -> Option<String> {
+        None
+    }
+
+    /// Move on to the statement's next result set; stored procedures and
+    /// multi-statement calls can return several. Afterwards `columns` and
+    /// `next_row` describe the new set. `false` once there are no more.
+    fn next_result_set(&mut self) -> BackendResult<bool> {
+        Ok(false)
+    }
+
+    /// Where cells too large for the tiles were put; see [`crate::cell_value`]
+    fn value_spill(&self) -> Option<Arc<ValueSpill>> {
+        None
+    }
+}
+
+/// The session's current role, warehouse, database and schema
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionContext {
+    pub role: Option<String>,
+    pub warehouse: Option<String>,
+    pub database: Option<String>,
+    pub schema: Option<String>,
+}
+
+impl SessionContext {
+    /// `ROLE · WAREHOUSE · DATABASE.SCHEMA`, with `–` for anything unset
+    pub fn label(&self) -> String {
+        let part = |v: &Option<String>| v.clone().unwrap_or_else(|| "–".to_string());
+        let namespace = match (&self.database, &self.schema) {
+            (Some(db), Some(schema)) => format!("{}.{}", db, schema),
+            (db, _) => part(db),
+        };
+        format!("{} · {} · {}", part(&self.role), part(&self.warehouse), namespace)
+    }
+}
+
+/// Outcome of executing one statement.
+pub enum Execution<'s> {
+    /// The statement produced a result set
+    Rows(Box<dyn RowStream + 's>),
+    /// No result set; `affected_rows` is `None` when the engine can't tell
+    NoRows { affected_rows: Option<i64>, query_id: Option<String> },
+}
+
+/// Cancels whatever statement the backend is currently executing.
+/// Must be callable from the UI thread while the worker is blocked.
+pub trait QueryCanceller: Send + Sync {
+    fn cancel(&self);
+}
+
+/// A database engine the DB worker can drive.
+pub trait QueryBackend: Send {
+    /// The connection string as configured, `${…}` placeholders and all
+    fn connection_string(&self) -> &str;
+
+    /// Open the connection to `conn_str`, the connection string with its
+    /// placeholders filled in. Called on the worker thread; use
+    /// [`connect_backend`] rather than calling it directly.
+    fn connect(&mut self, conn_str: &str) -> BackendResult<()>;
+
+    /// Run one SQL statement.
+    fn execute<'s>(&'s mut self, sql: &str) -> BackendResult<Execution<'s>>;
+
+    /// Handle used to cancel in-flight statements; valid before `connect`.
+    fn canceller(&self) -> Arc<dyn QueryCanceller>;
+
+    /// Whether standalone `DECLARE`/`BEGIN` blocks need the Snowflake
+    /// `EXECUTE IMMEDIATE $$ … $$` wrapper.
+    fn wraps_script_blocks(&self) -> bool {
+        false
+    }
+
+    /// Whether `\` escapes the next character inside string literals.
+    fn backslash_escapes(&self) -> bool {
+        false
+    }
+
+    /// Turn autocommit off (`manual`) or back on. Turning it back on commits
+    /// whatever is pending. A new connection always starts in autocommit.
+    fn set_manual_transactions(&mut self, _manual: bool) -> BackendResult<()> {
+        Err(BackendError::new("This backend does not support manual transactions"))
+    }
+
+    /// Commit the open transaction (manual mode only)
+    fn commit(&mut self) -> BackendResult<()> {
+        Err(BackendError::new("This backend does not support manual transactions"))
+    }
+
+    /// Roll back the open transaction (manual mode only)
+    fn rollback(&mut self) -> BackendResult<()> {
+        Err(BackendError::new("This backend does not support manual transactions"))
+    }
+
+    /// Another, not yet connected backend for the same database, used to run
+    /// statements in parallel. `None` if a second session can't see the same data.
+    fn new_connection(&self) -> Option<Box<dyn QueryBackend>> {
+        None
+    }
+
+    /// Ask the server for the session's current context; `None` if the
+    /// backend has no such notion or the lookup failed.
+    fn session_context(&mut self) -> Option<SessionContext> {
+        None
+    }
+
+    /// How binary and oversized values are turned into cell text. Backends
+    /// from `new_connection` inherit the options.
+    fn set_value_options(&mut self, _options: ValueOptions) {}
+}
+
+impl QueryBackend for Box<dyn QueryBackend> {
+    fn connection_string(&self) -> &str {
+        (**self).connection_string()
+    }
+
+    fn connect(&mut self, conn_str: &str) -> BackendResult<()> {
+        (**self).connect(conn_str)
+    }
+
+    fn execute<'s>(&'s mut self, sql: &str) -> BackendResult<Execution<'s>> {
+        (**self).execute(sql)
+    }
+
+    fn canceller(&self) -> Arc<dyn QueryCanceller> {
+        (**self).canceller()
+    }
+
+    fn wraps_script_blocks(&self) -> bool {
+        (**self).wraps_script_blocks()
+    }
+
+    fn backslash_escapes(&self) -> bool {
+        (**self).backslash_escapes()
+    }
+
+    fn set_manual_transactions(&mut self, manual: bool) -> BackendResult<()> {
+        (**self).set_manual_transactions(manual)
+    }
+
+    fn commit(&mut self) -> BackendResult<()> {
+        (**self).commit()
+    }
+
+    fn rollback(&mut self) -> BackendResult<()> {
+        (**self).rollback()
+    }
+
+    fn new_connection(&self) -> Option<Box<dyn QueryBackend>> {
+        (**self).new_connection()
+    }
+
+    fn session_context(&mut self) -> Option<SessionContext> {
+        (**self).session_context()
+    }
+
+    fn set_value_options(&mut self, options: ValueOptions) {
+        (**self).set_value_options(options)
+    }
+}
+
+/// Connect `backend` with the placeholders of its connection string filled
+/// in (see [`crate::secrets`]). The values are masked in the error, whatever
+/// the backend puts in it.
+pub fn connect_backend<B: QueryBackend + ?Sized>(backend: &mut B) -> BackendResult<()> {
+    let expanded = crate::secrets::expand(backend.connection_string()).map_err(|e| BackendError::new(e.to_string()))?;
+    backend
+        .connect(&expanded.text)
+        .map_err(|e| BackendError { message: expanded.redact(&e.message), ..e })
+}
+
+/// Pick the backend for a connection string: `sqlite:` targets a local
+/// SQLite database, anything else is handed to ODBC.
+pub fn backend_for(conn_str: &str) -> Box<dyn QueryBackend> {
+    if conn_str.trim_start().to_ascii_lowercase().starts_with(SQLITE_PREFIX) {
+        Box::new(SqliteBackend::new(conn_str))
+    } else {
+        Box::new(OdbcBackend::new(conn_str.to_string()))
+    }
+}
+
+//------- ODBC --------
+
+impl From<odbc::DiagnosticRecord> for BackendError {
+    fn from(rec: odbc::DiagnosticRecord) -> Self {
+        BackendError::new(rec.to_string())
+    }
+}
+
+/// Map the SQL type reported by `SQLDescribeCol` onto a [`ColumnType`]
+fn odbc_column_type(data_type: SqlDataType, decimal_digits: Option<u16>) -> ColumnType {
+    use SqlDataType::*;
+    match data_type {
+        SQL_INTEGER | SQL_SMALLINT | SQL_EXT_BIGINT | SQL_EXT_TINYINT => ColumnType::Integer,
+        SQL_NUMERIC | SQL_DECIMAL if decimal_digits.unwrap_or(0) == 0 => ColumnType::Integer,
+        SQL_NUMERIC | SQL_DECIMAL => ColumnType::Decimal,
+        SQL_FLOAT | SQL_REAL | SQL_DOUBLE => ColumnType::Float,
+        SQL_EXT_BIT => ColumnType::Boolean,
+        SQL_DATE | SQL_DATETIME => ColumnType::Date,
+        SQL_TIME | SQL_EXT_TIME_OR_INTERVAL | SQL_SS_TIME2 => ColumnType::Time,
+        SQL_TIMESTAMP | SQL_EXT_TIMESTAMP => ColumnType::Timestamp,
+        SQL_EXT_BINARY | SQL_EXT_VARBINARY | SQL_EXT_LONGVARBINARY => ColumnType::Binary,
+        _ => ColumnType::Text,
+    }
+}
+
+/// The ODBC environment is process-wide; connections borrow it for `'static`.
+struct SharedEnv(Environment<Version3>);
+unsafe impl Send for SharedEnv {}
+unsafe impl Sync for SharedEnv {}
+
+static ODBC_ENV: Lazy<Option<SharedEnv>> =
+    Lazy::new(|| create_environment_v3().ok().map(SharedEnv));
+
+#[derive(Clone, Copy)]
+pub struct SafeStmt(SQLHSTMT);
+unsafe impl Send for SafeStmt {}
+unsafe impl Sync for SafeStmt {}
+
+/// Calls `SQLCancel` on the statement that is *currently executing*.
+pub struct OdbcCanceller {
+    current_stmt: Arc<Mutex<Option<SafeStmt>>>,
+}
+
+impl QueryCanceller for OdbcCanceller {
+    fn cancel(&self) {
+        if let Some(h) = *self.current_stmt.lock().unwrap() {
+            unsafe { let _ = SQLCancel(h.0); };
+        }
+    }
+}
+
+/// The odbc crate tracks the autocommit mode in the connection's type
+enum OdbcConnection {
+    Autocommit(Connection<'static, AutocommitOn>),
+    Manual(Connection<'static, AutocommitOff>),
+}
+
+pub struct OdbcBackend {
+    conn_str: String,
+    conn: Option<OdbcConnection>,
+    // shared handle of the statement that is *currently executing*
+    current_stmt: Arc<Mutex<Option<SafeStmt>>>,
+    value_options: ValueOptions,
+}
+
+// The connection is only opened and used on the worker thread; the backend is
+// moved there before `connect` is called.
+unsafe impl Send for OdbcBackend {}
+
+impl OdbcBackend {
+    pub fn new(conn_str: String) -> Self {
+        Self {
+            conn_str,
+            conn: None,
+            current_stmt: Arc::new(Mutex::new(None)),
+            value_options: ValueOptions::default(),
+        }
+    }
+}
+
+impl QueryBackend for OdbcBackend {
+    fn connection_string(&self) -> &str {
+        &self.conn_str
+    }
+
+    fn connect(&mut self, conn_str: &str) -> BackendResult<()> {
+        let env = ODBC_ENV
+            .as_ref()
+            .ok_or_else(|| BackendError::new("Failed to create ODBC environment"))?;
+        self.conn = None;  // reconnecting: let go of the dead connection first
+        let conn = env.0.connect_with_connection_string(conn_str)?;
+
+        // Enable all secondary roles by default
+        if let Ok(stmt) = Statement::with_parent(&conn) {
+            let _ = stmt.exec_direct("USE SECONDARY ROLES ALL");
+        }
+
+        self.conn = Some(OdbcConnection::Autocommit(conn));
+        Ok(())
+    }
+
+    fn execute<'s>(&'s mut self, sql: &str) -> BackendResult<Execution<'s>> {
+        match self.conn.as_ref().ok_or_else(|| BackendError::new("Not connected"))? {
+            OdbcConnection::Autocommit(conn) => execute_on(conn, sql, &self.current_stmt, self.value_options),
+            OdbcConnection::Manual(conn) => execute_on(conn, sql, &self.current_stmt, self.value_options),
+        }
+    }
+
+    fn canceller(&self) -> Arc<dyn QueryCanceller> {
+        Arc::new(OdbcCanceller { current_stmt: Arc::clone(&self.current_stmt) })
+    }
+
+    fn wraps_script_blocks(&self) -> bool {
+        true
+    }
+
+    fn backslash_escapes(&self) -> bool {
+        true
+    }
+
+    fn set_manual_transactions(&mut self, manual: bool) -> BackendResult<()> {
+        let conn = self.conn.take().ok_or_else(|| BackendError::new("Not connected"))?;
+        let (conn, switched) = match (conn, manual) {
+            (OdbcConnection::Autocommit(c), true) => match c.disable_autocommit() {
+                Ok(c) => (OdbcConnection::Manual(c), true),
+                Err(c) => (OdbcConnection::Autocommit(c), false),
+            },
+            (OdbcConnection::Manual(c), false) => match c.enable_autocommit() {
+                Ok(c) => (OdbcConnection::Autocommit(c), true),
+                Err(c) => (OdbcConnection::Manual(c), false),
+            },
+            (unchanged, _) => (unchanged, true),
+        };
+        self.conn = Some(conn);
+        if switched {
+            Ok(())
+        } else {
+            Err(BackendError::new("The ODBC driver refused to change the autocommit mode"))
+        }
+    }
+
+    fn commit(&mut self) -> BackendResult<()> {
+        match self.conn.as_mut() {
+            Some(OdbcConnection::Manual(conn)) => Ok(conn.commit()?),
+            Some(OdbcConnection::Autocommit(_)) => Err(BackendError::new("Autocommit is on; there is no transaction to commit")),
+            None => Err(BackendError::new("Not connected")),
+        }
+    }
+
+    fn rollback(&mut self) -> BackendResult<()> {
+        match self.conn.as_mut() {
+            Some(OdbcConnection::Manual(conn)) => Ok(conn.rollback()?),
+            Some(OdbcConnection::Autocommit(_)) => Err(BackendError::new("Autocommit is on; there is no transaction to roll back")),
+            None => Err(BackendError::new("Not connected")),
+        }
+    }
+
+    fn new_connection(&self) -> Option<Box<dyn QueryBackend>> {
+        let mut backend = OdbcBackend::new(self.conn_str.clone());
+        backend.value_options = self.value_options;
+        Some(Box::new(backend))
+    }
+
+    fn session_context(&mut self) -> Option<SessionContext> {
+        match self.conn.as_ref()? {
+            OdbcConnection::Autocommit(conn) => session_context_on(conn),
+            OdbcConnection::Manual(conn) => session_context_on(conn),
+        }
+    }
+
+    fn set_value_options(&mut self, options: ValueOptions) {
+        self.value_options = options;
+    }
+}
+
+/// Run one statement on either kind of connection
+fn execute_on<'s, AC: AutocommitMode + 's>(
+    conn: &'s Connection<'static, AC>,
+    sql: &str,
+    current_stmt: &Arc<Mutex<Option<SafeStmt>>>,
+    options: ValueOptions,
+) -> BackendResult<Execution<'s>> {
+    let stmt = Statement::with_parent(conn)?;
+
+    // 🔗 expose raw HSTMT so Ctrl-Backspace can call SQLCancel
+    unsafe {
+        *current_stmt.lock().unwrap() = Some(SafeStmt(stmt.handle()));
+    }
+
+    match stmt.exec_direct(sql) {
+        Ok(Data(stmt)) => {
+            let columns = describe_columns(&stmt)?;
+            Ok(Execution::Rows(Box::new(OdbcRows {
+                stmt,
+                columns,
+                current_stmt: Arc::clone(current_stmt),
+                query_id: last_query_id(conn),
+                options,
+                spill: Arc::new(ValueSpill::default()),
+                buf: vec![0; GET_DATA_CHUNK],
+            })))
+        }
+        Ok(NoData(stmt)) => {
+            let affected_rows = stmt.affected_row_count().ok()
+                .filter(|&cnt| cnt >= 0)
+                .map(|cnt| cnt as i64);
+            *current_stmt.lock().unwrap() = None;
+            Ok(Execution::NoRows { affected_rows, query_id: last_query_id(conn) })
+        }
+        Err(e) => {
+            *current_stmt.lock().unwrap() = None;
+            let mut err = BackendError::from(e);
+            if !err.is_connection_lost() {
+                err.query_id = last_query_id(conn);
+            }
+            Err(err)
+        }
+    }
+}
+
+/// Columns of the result set the statement is positioned on
+fn describe_columns<AC: AutocommitMode>(
+    stmt: &Statement<'_, '_, odbc::Executed, odbc::HasResult, AC>,
+) -> BackendResult<Vec<ColumnInfo>> {
+    let cols = stmt.num_result_cols()?;
+    let mut columns = Vec::with_capacity(cols.max(0) as usize);
+    for i in 1..=cols {
+        let desc = stmt.describe_col(i as u16)?;
+        columns.push(ColumnInfo {
+            col_type: odbc_column_type(desc.data_type, desc.decimal_digits),
+            name: desc.name,
+        });
+    }
+    Ok(columns)
+}
+
+fn session_context_on<AC: AutocommitMode>(conn: &Connection<'static, AC>) -> Option<SessionContext> {
+    let stmt = Statement::with_parent(conn).ok()?;
+    let sql = "SELECT CURRENT_ROLE(), CURRENT_WAREHOUSE(), CURRENT_DATABASE(), CURRENT_SCHEMA()";
+    match stmt.exec_direct(sql).ok()? {
+        Data(mut stmt) => {
+            let mut cursor = stmt.fetch().ok()??;
+            let mut get = |col: u16| cursor.get_data::<String>(col).ok().flatten().filter(|v| !v.is_empty());
+            Some(SessionContext { role: get(1), warehouse: get(2), database: get(3), schema: get(4) })
+        }
+        NoData(_) => None,
+    }
+}
+
+/// Snowflake's id for the statement that just ran on `conn`. Asked on a
+/// second statement handle, so a result set can still be open.
+fn last_query_id<AC: AutocommitMode>(conn: &Connection<'static, AC>) -> Option<String> {
+    let stmt = Statement::with_parent(conn).ok()?;
+    match stmt.exec_direct("SELECT LAST_QUERY_ID()").ok()? {
+        Data(mut stmt) => {
+            let mut cursor = stmt.fetch().ok()??;
+            cursor.get_data::<String>(1).ok()?.filter(|id| !id.is_empty())
+        }
+        NoData(_) => None,
+    }
+}
+
+struct OdbcRows<'s, AC: AutocommitMode> {
+    stmt: Statement<'s, 's, odbc::Executed, odbc::HasResult, AC>,
+    columns: Vec<ColumnInfo>,
+    current_stmt: Arc<Mutex<Option<SafeStmt>>>,
+    query_id: Option<String>,
+    options: ValueOptions,
+    spill: Arc<ValueSpill>,
+    /// `SQLGetData` target, reused across cells
+    buf: Vec<u8>,
+}
+
+/// Buffer size for reading a cell with `SQLGetData`
+const GET_DATA_CHUNK: usize = 32 * 1024;
+
+impl<AC: AutocommitMode> OdbcRows<'_, AC> {
+    /// Read column `col` (1-based) of the current row piece by piece, so a
+    /// large value can go to the spill file instead of being held whole.
+    /// The odbc crate's `get_data` has no way to do this.
+    fn read_cell(&self, col: u16, buf: &mut [u8]) -> BackendResult<Option<String>> {
+        let binary = self.columns[col as usize - 1].col_type == ColumnType::Binary;
+        let c_type = if binary { SqlCDataType::SQL_C_BINARY } else { SqlCDataType::SQL_C_CHAR };
+        // character data comes NUL-terminated
+        let room = if binary { buf.len() } else { buf.len() - 1 };
+        let mut cell = CellBuilder::new(self.options, &self.spill, binary);
+        loop {
+            let mut indicator: SQLLEN = 0;
+            let ret = unsafe {
+                SQLGetData(
+                    self.stmt.handle(),
+                    col,
+                    c_type,
+                    buf.as_mut_ptr() as SQLPOINTER,
+                    buf.len() as SQLLEN,
+                    &mut indicator,
+                )
+            };
+            match ret {
+                SQLRETURN::SQL_SUCCESS | SQLRETURN::SQL_SUCCESS_WITH_INFO => {}
+                SQLRETURN::SQL_NO_DATA => break,
+                _ => {
+                    return Err(self.stmt.get_diag_rec(1)
+                        .map(BackendError::from)
+                        .unwrap_or_else(|| BackendError::new("Reading a value failed")))
+                }
+            }
+            if indicator == SQL_NULL_DATA {
+                return Ok(None);
+            }
+            let len = if indicator == SQL_NO_TOTAL { room } else { (indicator as usize).min(room) };
+            cell.push(&buf[..len])
+                .map_err(|e| BackendError::new(format!("Could not store a large value: {}", e)))?;
+            // with info = the buffer was too small and the rest follows
+            if ret == SQLRETURN::SQL_SUCCESS {
+                break;
+            }
+        }
+        Ok(Some(cell.finish()))
+    }
+}
+
+impl<AC: AutocommitMode> RowStream for OdbcRows<'_, AC> {
+    fn columns(&self) -> &[ColumnInfo] {
+        &self.columns
+    }
+
+    fn next_row(&mut self) -> BackendResult<Option<Vec<Option<String>>>> {
+        // the cursor is only a borrow of the statement; cells are read off the raw handle
+        if self.stmt.fetch()?.is_none() {
+            return Ok(None);
+        }
+        let mut buf = std::mem::take(&mut self.buf);
+        let row = (0..self.columns.len())
+            .map(|idx| self.read_cell(idx as u16 + 1, &mut buf))
+            .collect::<BackendResult<Vec<_>>>();
+        self.buf = buf;
+        row.map(Some)
+    }
+
+    fn query_id(&self) -> Option<String> {
+        self.query_id.clone()
+    }
+
+    fn next_result_set(&mut self) -> BackendResult<bool> {
+        loop {
+            // the odbc crate has no wrapper for SQLMoreResults
+            match unsafe { SQLMoreResults(self.stmt.handle()) } {
+                SQLRETURN::SQL_SUCCESS | SQLRETURN::SQL_SUCCESS_WITH_INFO => {}
+                SQLRETURN::SQL_NO_DATA => return Ok(false),
+                _ => {
+                    return Err(self.stmt.get_diag_rec(1)
+                        .map(BackendError::from)
+                        .unwrap_or_else(|| BackendError::new("Fetching the next result set failed")))
+                }
+            }
+            // row counts of statements without a result set are skipped
+            if self.stmt.num_result_cols()? > 0 {
+                self.columns = describe_columns(&self.stmt)?;
+                return Ok(true);
+            }
+        }
+    }
+
+    fn value_spill(&self) -> Option<Arc<ValueSpill>> {
+        Some(Arc::clone(&self.spill))
+    }
+}
+
+impl<AC: AutocommitMode> Drop for OdbcRows<'_, AC> {
+    fn drop(&mut self) {
+        // clear handle – we're done with it (the statement is freed right after)
+        *self.current_stmt.lock().unwrap() = None;
+    }
+}
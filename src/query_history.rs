@@ -0,0 +1,145 @@
+//! Every statement Frost runs, kept in a local SQLite database
+//! (`frost_history.db` next to Frost.toml) and browsed with F3.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::Result;
+use rusqlite::{params, Connection};
+
+/// How many entries the browser loads when it opens
+pub const BROWSER_LIMIT: usize = 5000;
+
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    pub executed_at: String,
+    pub profile: Option<String>,
+    pub role: Option<String>,
+    pub elapsed_ms: Option<i64>,
+    pub row_count: Option<i64>,
+    pub error: Option<String>,
+    pub query_context: String,
+    pub sql: String,
+}
+
+/// What the workspace knows about a statement once it has completed
+pub struct CompletedStatement<'a> {
+    pub sql: &'a str,
+    pub query_context: &'a str,
+    pub profile: Option<&'a str>,
+    pub role: Option<&'a str>,
+    pub elapsed: Option<Duration>,
+    pub row_count: Option<usize>,
+    pub error: Option<&'a str>,
+}
+
+pub struct QueryHistory {
+    conn: Connection,
+}
+
+impl QueryHistory {
+    /// The history database in the executable directory, like Frost.toml
+    pub fn default_path() -> PathBuf {
+        std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent().map(|p| p.to_path_buf()))
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("frost_history.db")
+    }
+
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS history (
+                 id            INTEGER PRIMARY KEY,
+                 executed_at   TEXT NOT NULL,
+                 profile       TEXT,
+                 role          TEXT,
+                 elapsed_ms    INTEGER,
+                 row_count     INTEGER,
+                 error         TEXT,
+                 query_context TEXT NOT NULL,
+                 sql           TEXT NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS history_executed_at ON history (executed_at);",
+        )?;
+        Ok(Self { conn })
+    }
+
+    /// Store a completed statement; returns its id for [`QueryHistory::update_result`]
+    pub fn record(&self, e: &CompletedStatement) -> Result<i64> {
+        let executed_at = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        self.conn.execute(
+            "INSERT INTO history (executed_at, profile, role, elapsed_ms, row_count, error, query_context, sql)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                executed_at,
+                e.profile,
+                e.role,
+                e.elapsed.map(|d| d.as_millis() as i64),
+                e.row_count.map(|n| n as i64),
+                e.error,
+                e.query_context,
+                e.sql,
+            ],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Fetching more rows of a capped result changes its row count and time
+    pub fn update_result(&self, id: i64, elapsed: Duration, row_count: Option<usize>) -> Result<()> {
+        self.conn.execute(
+            "UPDATE history SET elapsed_ms = ?2, row_count = ?3 WHERE id = ?1",
+            params![id, elapsed.as_millis() as i64, row_count.map(|n| n as i64)],
+        )?;
+        Ok(())
+    }
+
+    /// Newest first
+    pub fn recent(&self, limit: usize) -> Result<Vec<HistoryEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT executed_at, profile, role, elapsed_ms, row_count, error, query_context, sql
+             FROM history ORDER BY id DESC LIMIT ?1",
+        )?;
+        let rows = stmt.query_map([limit as i64], |r| {
+            Ok(HistoryEntry {
+                executed_at: r.get(0)?,
+                profile: r.get(1)?,
+                role: r.get(2)?,
+                elapsed_ms: r.get(3)?,
+                row_count: r.get(4)?,
+                error: r.get(5)?,
+                query_context: r.get(6)?,
+                sql: r.get(7)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    }
+}
+
+/// Score `text` against a search pattern: every whitespace-separated term has
+/// to appear as a case-insensitive subsequence. Consecutive characters and
+/// matches at word starts score higher; `None` if any term doesn't match.
+pub fn fuzzy_score(pattern: &str, text: &str) -> Option<i64> {
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    let mut total = 0;
+    for term in pattern.split_whitespace() {
+        let mut score = 0i64;
+        let mut pos = 0usize;
+        let mut prev_hit: Option<usize> = None;
+        for pc in term.chars().flat_map(char::to_lowercase) {
+            let hit = (pos..text.len()).find(|&i| text[i] == pc)?;
+            score += 1;
+            if prev_hit.is_some_and(|p| p + 1 == hit) {
+                score += 5;
+            }
+            if hit == 0 || !text[hit - 1].is_alphanumeric() {
+                score += 3;
+            }
+            prev_hit = Some(hit);
+            pos = hit + 1;
+        }
+        total += score;
+    }
+    Some(total)
+}
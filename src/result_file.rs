@@ -0,0 +1,124 @@
+//! `.frostres` files: a finished result tab saved with its rows and where it
+//! came from, so it can be reopened (`Ctrl+O`, `--open-result`) without
+//! running the query again.
+//!
+//! Layout: the tile file exactly as [`TileRowStore`] wrote it, the spilled
+//! oversized values, a JSON description, and a fixed 32-byte trailer holding
+//! the three section lengths and `FROSTRES`.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use serde::{Deserialize, Serialize};
+
+use crate::cell_value::ValueSpill;
+use crate::tile_rowstore::TileRowStore;
+
+pub const EXTENSION: &str = "frostres";
+
+const TRAILER_MAGIC: &[u8; 8] = b"FROSTRES";
+const TRAILER_LEN: u64 = 32;
+/// 2: checksummed tiles; 3: encoded and compressed tiles; 4: tiles stored
+/// column by column; 5: spilled values have a cell type of their own
+const FORMAT_VERSION: u32 = 5;
+
+/// What a saved result describes about itself
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResultInfo {
+    pub version: u32,
+    pub headers: Vec<String>,
+    /// Tab label when it was saved
+    pub query_context: String,
+    pub sql: Option<String>,
+    pub query_id: Option<String>,
+    /// RFC 3339, local time
+    pub saved_at: String,
+    pub rows: usize,
+    /// Fetching stopped at the row cap, so `rows` isn't the whole result
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+impl ResultInfo {
+    pub fn new(headers: Vec<String>, query_context: String, sql: Option<String>, query_id: Option<String>, rows: usize, truncated: bool) -> Self {
+        Self {
+            version: FORMAT_VERSION,
+            headers,
+            query_context,
+            sql,
+            query_id,
+            saved_at: chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, false),
+            rows,
+            truncated,
+        }
+    }
+}
+
+fn invalid(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+/// Write `store` and `info` to `path`
+pub fn save(path: &Path, info: &ResultInfo, store: &TileRowStore) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    let tiles_len = store.write_tiles_to(&mut out)?;
+    let spill_len = match store.spill() {
+        Some(spill) => spill.write_to(&mut out)?,
+        None => 0,
+    };
+    let json = serde_json::to_vec_pretty(info).map_err(|e| invalid(e.to_string()))?;
+    out.write_all(&json)?;
+    out.write_u64::<LittleEndian>(tiles_len)?;
+    out.write_u64::<LittleEndian>(spill_len)?;
+    out.write_u64::<LittleEndian>(json.len() as u64)?;
+    out.write_all(TRAILER_MAGIC)?;
+    out.flush()
+}
+
+/// Read a file written by [`save`]
+pub fn open(path: &Path) -> io::Result<(ResultInfo, TileRowStore)> {
+    let mut file = File::open(path)?;
+    let file_len = file.metadata()?.len();
+    if file_len < TRAILER_LEN {
+        return Err(invalid("Not a Frost result file (too short)"));
+    }
+    file.seek(SeekFrom::Start(file_len - TRAILER_LEN))?;
+    let tiles_len = file.read_u64::<LittleEndian>()?;
+    let spill_len = file.read_u64::<LittleEndian>()?;
+    let json_len = file.read_u64::<LittleEndian>()?;
+    let mut magic = [0u8; 8];
+    file.read_exact(&mut magic)?;
+    if &magic != TRAILER_MAGIC {
+        return Err(invalid("Not a Frost result file"));
+    }
+    let sections = tiles_len
+        .checked_add(spill_len)
+        .and_then(|n| n.checked_add(json_len))
+        .and_then(|n| n.checked_add(TRAILER_LEN));
+    if sections != Some(file_len) {
+        return Err(invalid("Frost result file is truncated or damaged"));
+    }
+
+    file.seek(SeekFrom::Start(tiles_len + spill_len))?;
+    let mut json = vec![0u8; json_len as usize];
+    file.read_exact(&mut json)?;
+    let info: ResultInfo = serde_json::from_slice(&json).map_err(|e| invalid(format!("Bad result description: {}", e)))?;
+    if info.version > FORMAT_VERSION {
+        return Err(invalid(format!("Saved by a newer Frost (format version {})", info.version)));
+    }
+    if info.version < FORMAT_VERSION {
+        return Err(invalid(format!("Saved by an older Frost (format version {}); run the query again to reopen it", info.version)));
+    }
+
+    let mut store = TileRowStore::open_saved(path, tiles_len)?;
+    if store.ncols != info.headers.len() || store.nrows != info.rows {
+        return Err(invalid("Result description doesn't match its rows"));
+    }
+    if spill_len > 0 {
+        store.set_spill(Some(Arc::new(ValueSpill::open_saved(path, tiles_len, spill_len)?)));
+    }
+    Ok((info, store))
+}
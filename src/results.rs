@@ -6,24 +6,36 @@ use tui::{
     Frame, 
 };
 
-use copypasta::ClipboardContext;
+use copypasta::{ClipboardContext, ClipboardProvider};
 use std::time::{Duration, Instant};
 
 #[derive(Debug)]
 pub enum ResultsContent {
     Table {
         headers: Vec<String>,
+        column_types: Vec<crate::column_type::ColumnType>,
         tile_store: crate::tile_rowstore::TileRowStore,
     },
     Error {
         message: String,
         cursor: usize,           // Add cursor position
         selection: Option<(usize, usize)>, // Add selection range
+        /// Where `e` moves the editor caret, when the error gives a position
+        location: Option<crate::sql_error::ErrorLocation>,
     },
     Info {
         message: String, // <-- New variant
     },
+    /// Cancelled by the statement timeout
+    TimedOut {
+        timeout: Duration,
+    },
     Pending, // For a tab that's running and not yet populated
+    /// A run waiting for the worker; `position` is 1-based
+    Queued {
+        position: usize,
+        sql: String,
+    },
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -31,6 +43,29 @@ pub enum ScrollDirection {
     None,
     Left,
     Right,
+    Up,
+    Down,
+}
+
+impl ScrollDirection {
+    /// Which tiles to decode ahead of the view
+    pub fn read_ahead(self) -> crate::tile_rowstore::ReadAhead {
+        use crate::tile_rowstore::ReadAhead;
+        match self {
+            ScrollDirection::Down => ReadAhead::Forward,
+            ScrollDirection::Up => ReadAhead::Backward,
+            ScrollDirection::None | ScrollDirection::Left | ScrollDirection::Right => ReadAhead::Both,
+        }
+    }
+}
+
+/// Set on a tab whose result set stopped at the row cap
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Truncation {
+    /// The cursor is still open; more rows can be fetched
+    Resumable,
+    /// The cursor was closed (a later statement needed the connection)
+    Closed,
 }
 
 pub struct ResultsTab {
@@ -43,12 +78,21 @@ pub struct ResultsTab {
     pub running: bool,
     pub elapsed: Option<Duration>,
     pub run_started: Option<Instant>,
-    pub visible_cache: Option<(usize, Vec<Vec<String>>)>,
+    /// Live row counter while the result set is still being fetched
+    pub rows_fetched: Option<usize>,
+    pub truncated: Option<Truncation>,
     pub summary_cache: Option<(
         crate::results_selection::ResultSelection,
         (String, Option<String>)
     )>,
     pub query_context: String,
+    /// Statement index within the worker's current run; `None` once a newer
+    /// run has started (and for queued tabs)
+    pub query_idx: Option<usize>,
+    /// Server-side id of the statement (Snowflake's `LAST_QUERY_ID()`)
+    pub query_id: Option<String>,
+    /// The statement that produced the tab, as sent to the server
+    pub sql: Option<String>,
     pub column_widths_cache: Option<crate::results_selection::ColumnWidths>,
     pub scroll_direction: ScrollDirection,
     pub scroll_x: u16,
@@ -70,14 +114,52 @@ impl ResultsTab {
             running: true,
             elapsed: None,
             run_started: Some(started),
-            visible_cache: None,
+            rows_fetched: None,
+            truncated: None,
             summary_cache: None,
             query_context,
+            query_idx: None,
+            query_id: None,
+            sql: None,
             column_widths_cache: None,
             scroll_direction: ScrollDirection::None,
             scroll_x: 0,
         }
     }
+
+    /// Placeholder for a run that starts once the worker is free
+    pub fn new_queued(query_context: String, position: usize, sql: String) -> Self {
+        Self {
+            content: ResultsContent::Queued { position, sql },
+            running: false,
+            run_started: None,
+            ..Self::new_pending(query_context)
+        }
+    }
+
+    /// A finished tab for a result read back from a `.frostres` file
+    pub fn from_saved(label: String, info: crate::result_file::ResultInfo, tile_store: crate::tile_rowstore::TileRowStore) -> Self {
+        let column_types = tile_store.column_types.clone();
+        Self {
+            content: ResultsContent::Table { headers: info.headers, column_types, tile_store },
+            running: false,
+            run_started: None,
+            query_id: info.query_id,
+            sql: info.sql,
+            // the cursor it came from is long gone
+            truncated: info.truncated.then_some(Truncation::Closed),
+            ..Self::new_pending(label)
+        }
+    }
+
+    pub fn is_queued(&self) -> bool {
+        matches!(self.content, ResultsContent::Queued { .. })
+    }
+
+    /// The statement failed or hit the statement timeout
+    pub fn is_failed(&self) -> bool {
+        matches!(self.content, ResultsContent::Error { .. } | ResultsContent::TimedOut { .. })
+    }
 }
 
 pub struct Results {
@@ -117,25 +199,6 @@ impl ResultsTab {
         // Note: Horizontal viewport adjustment is now handled directly in the keyboard handler
         // to work properly with variable column widths
     }
-    
-    pub fn visible_rows<'a>(
-        &'a mut self,
-        tile_store: &mut crate::tile_rowstore::TileRowStore,
-        max_rows: usize,
-    ) -> &'a [Vec<String>] {
-        let need_fetch = match self.visible_cache {
-            Some((cached_start, ref v)) => cached_start != self.view_row || v.is_empty(),
-            None => true,
-        };
-        if need_fetch {
-            let rows = tile_store
-                .get_rows(self.view_row, max_rows)
-                .unwrap_or_default();
-            self.visible_cache = Some((self.view_row, rows));
-        }
-        // Safe: we keep the Vec inside self.visible_cache for the whole frame
-        &self.visible_cache.as_ref().unwrap().1
-    }
 } 
 
 impl Results {
@@ -160,7 +223,7 @@ impl Results {
             return None;
         }
         let tab = &mut self.tabs[self.tab_idx];
-        if let ResultsContent::Table { headers, tile_store } = &mut tab.content {
+        if let ResultsContent::Table { headers, column_types, tile_store } = &mut tab.content {
             // return cached if unchanged
             if let Some((ref old_sel, ref tup)) = tab.summary_cache {
                 if old_sel == &tab.selection {
@@ -171,6 +234,7 @@ impl Results {
             let new_sum = crate::results_selection::compute_selection_summary(
                 &tab.selection,
                 headers,
+                column_types,
                 tile_store,
             );
             if let Some(ref s) = new_sum {
@@ -182,27 +246,79 @@ impl Results {
         }
     }
 
-    pub fn clear(&mut self) {
-        self.tabs.clear();
-        self.tab_idx = 0;
+    /// Move to the next (or previous) failed tab, wrapping around
+    pub fn jump_to_failed_tab(&mut self, forward: bool) -> Result<String, String> {
+        let failed: Vec<usize> = self.tabs.iter()
+            .enumerate()
+            .filter(|(_, t)| t.is_failed())
+            .map(|(i, _)| i)
+            .collect();
+        let (&first, &last) = failed.first().zip(failed.last()).ok_or("No failed result tabs")?;
+        let next = if forward {
+            failed.iter().copied().find(|&i| i > self.tab_idx).unwrap_or(first)
+        } else {
+            failed.iter().rev().copied().find(|&i| i < self.tab_idx).unwrap_or(last)
+        };
+        self.tab_idx = next;
+        self.tabs[next].scroll_direction = ScrollDirection::None;
+        let nth = failed.iter().position(|&i| i == next).unwrap_or(0) + 1;
+        Ok(format!("Failed tab {} of {}", nth, failed.len()))
+    }
+
+    /// Put the current tab's query ID on the clipboard
+    pub fn copy_query_id(&mut self) -> Result<String, String> {
+        let tab = self.tabs.get(self.tab_idx).ok_or("No result tab open")?;
+        let id = tab.query_id.clone().ok_or("No query ID for this tab")?;
+        self.clipboard
+            .set_contents(id.clone())
+            .map_err(|e| format!("Could not copy the query ID: {}", e))?;
+        Ok(format!("Copied query ID {}", id))
     }
 
-    /// Call this to create an empty tab for a pending/running query.
-    pub fn add_pending_tab(&mut self, query_context: String) {
-        self.tabs.push(ResultsTab::new_pending(query_context));
-        self.tab_idx = self.tabs.len() - 1;
+    /// Save the current table tab as a `.frostres` file
+    pub fn save_current_tab(&mut self, path: &std::path::Path) -> Result<String, String> {
+        let tab = self.tabs.get(self.tab_idx).ok_or("No result tab open")?;
+        let ResultsContent::Table { headers, tile_store, .. } = &tab.content else {
+            return Err("No table loaded, nothing to save.".to_string());
+        };
+        if tile_store.is_live() {
+            return Err("Rows are still being fetched; save when the query finishes (N fetches the rest)".to_string());
+        }
+        let info = crate::result_file::ResultInfo::new(
+            headers.clone(),
+            tab.query_context.clone(),
+            tab.sql.clone(),
+            tab.query_id.clone(),
+            tile_store.nrows,
+            tab.truncated.is_some(),
+        );
+        crate::result_file::save(path, &info, tile_store).map_err(|e| format!("Could not save the result: {}", e))?;
+        Ok(format!("Saved {} rows to {}", tile_store.nrows, path.display()))
     }
 
-    /// When a query finishes, fill in the tab with its data or error.
-    pub fn finish_tab(&mut self, tab_idx: usize, content: ResultsContent) {
-        if let Some(tab) = self.tabs.get_mut(tab_idx) {
-            tab.content = content;
-            tab.elapsed = tab.run_started.map(|start| start.elapsed());
-            tab.running = false;
-            tab.run_started = None;
-            tab.summary_cache = None;
-            // Note: column_widths_cache will be calculated when the table is rendered
+    /// Column name, row number and complete text of the cell under the
+    /// cursor. Oversized values are only read back from disk here.
+    pub fn cursor_cell_value(&mut self) -> Result<(String, String), String> {
+        let tab = self.tabs.get_mut(self.tab_idx).ok_or("No result tab open")?;
+        let col = tab.cursor_col.checked_sub(1).ok_or("Move to a data column to inspect a cell")?;
+        let row = tab.cursor_row;
+        let ResultsContent::Table { headers, tile_store, .. } = &mut tab.content else {
+            return Err("No table loaded, nothing to inspect.".to_string());
+        };
+        let cell = tile_store
+            .get_rows(row, 1)
+            .map_err(|e| e.to_string())?
+            .pop()
+            .and_then(|cells| cells.into_iter().nth(col))
+            .ok_or("No cell under the cursor")?;
+        let title = format!("{} · row {}", headers.get(col).map(String::as_str).unwrap_or("?"), row + 1);
+        if cell == crate::tile_rowstore::NULL_SENTINEL {
+            return Ok((title, "NULL".to_string()));
         }
+        let text = tile_store
+            .full_value(&cell)
+            .map_err(|e| format!("Could not read the value: {}", e))?;
+        Ok((title, text))
     }
 
     pub fn handle_key(&mut self, key: crossterm::event::KeyEvent) {
@@ -216,4 +332,4 @@ impl Results {
     pub fn render<B: Backend>(&mut self, f: &mut Frame<B>, area: tui::layout::Rect, total_queries: usize) {
         results_selection::render(self, f, area, total_queries)
     }
-}
\ No newline at end of file
+}
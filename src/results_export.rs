@@ -3,10 +3,12 @@
 //!   • clipboard/tab‐copy shows empty cells
 //!   • CSV export writes empty fields
 
+use crate::cell_value::{self, LargeValue};
 use crate::results_selection::{ResultSelection, SelectionKind};
-use crate::tile_rowstore::NULL_SENTINEL;
+use crate::tile_rowstore::{TileRowStore, NULL_SENTINEL, TILE_SIZE};
 
 use std::cmp::{max, min};
+use std::io::{self, Write};
 
 /// Convert sentinel → empty string (all other values passthrough)
 #[inline]
@@ -145,30 +147,43 @@ pub fn copy_selection(
     }
 }
 
-/// Export the **entire** result set as CSV (RFC-4180)
-pub fn export_entire_result_set(
-    headers: &Vec<String>,
-    data: &Vec<Vec<String>>,
-) -> String {
-    let mut csv_lines = Vec::with_capacity(data.len() + 1);
-
+/// Export the **entire** result set as CSV (RFC-4180), one tile of rows
+/// at a time so only that much is ever in memory
+pub fn write_entire_result_set(
+    out: &mut impl Write,
+    headers: &[String],
+    tile_store: &mut TileRowStore,
+) -> io::Result<()> {
     /* header */
-    csv_lines.push(
-        headers.iter()
-            .map(|h| escape_csv(clean(h)))
-            .collect::<Vec<_>>()
-            .join(","),
-    );
-
-    /* rows */
-    for row in data {
-        csv_lines.push(
-            row.iter()
-                .map(|c| escape_csv(clean(c)))
-                .collect::<Vec<_>>()
-                .join(","),
-        );
+    let header = headers.iter()
+        .map(|h| escape_csv(clean(h)))
+        .collect::<Vec<_>>()
+        .join(",");
+    out.write_all(header.as_bytes())?;
+
+    /* rows, read column by column */
+    let mut start = 0;
+    while start < tile_store.nrows {
+        let end = min(start + TILE_SIZE, tile_store.nrows);
+        let columns = (0..tile_store.ncols)
+            .map(|col| tile_store.get_column(col, start..end))
+            .collect::<io::Result<Vec<_>>>()?;
+        for row in 0..end - start {
+            out.write_all(b"\r\n")?;
+            for (i, column) in columns.iter().enumerate() {
+                if i > 0 {
+                    out.write_all(b",")?;
+                }
+                let cell = &column[row];
+                let field = if LargeValue::parse(cell).is_some() {
+                    escape_csv(&tile_store.full_value(cell)?)
+                } else {
+                    escape_csv(clean(cell_value::unescape(cell)))
+                };
+                out.write_all(field.as_bytes())?;
+            }
+        }
+        start = end;
     }
-
-    csv_lines.join("\r\n")
+    Ok(())
 }
@@ -19,7 +19,7 @@ use std::cmp::{max, min};
 use std::collections::HashMap;
 use copypasta::ClipboardProvider;
 
-use crate::results::{Results, ResultsContent, ScrollDirection};
+use crate::results::{Results, ResultsContent, ScrollDirection, Truncation};
 use crate::results_export::copy_selection;
 
 /// Column width limits
@@ -220,10 +220,11 @@ impl ColumnWidths {
             // Check sample rows for this column
             for row in sample_rows.iter().take(100) { // Sample more rows for better width estimation
                 if let Some(cell) = row.get(col_idx) {
+                    let shown = crate::cell_value::display_text(cell);
                     let cell_str = if cell == crate::tile_rowstore::NULL_SENTINEL {
                         "NULL"
                     } else {
-                        cell
+                        &shown
                     };
                     // Account for display length
                     let display_len = cell_str.chars().take(MAX_COL_WIDTH as usize).count();
@@ -471,7 +472,7 @@ fn update_find_matches(results: &mut Results) {
     if results.tabs.is_empty() { return; }
     let tab = &mut results.tabs[results.tab_idx];
     
-    if let ResultsContent::Table { headers, tile_store } = &mut tab.content {
+    if let ResultsContent::Table { headers, tile_store, .. } = &mut tab.content {
         let query_lower = results.find_query.to_lowercase();
         
         // Search headers - NOW INSIDE THE if let BLOCK
@@ -489,25 +490,26 @@ fn update_find_matches(results: &mut Results) {
         let start_row = tab.cursor_row.saturating_sub(SEARCH_WINDOW / 2);
         let end_row = (start_row + SEARCH_WINDOW).min(tile_store.nrows);
         
-        let rows = tile_store.get_rows(start_row, end_row - start_row)
-            .unwrap_or_default();
-        
-        for (offset, row_data) in rows.iter().enumerate() {
-            let row_idx = start_row + offset;
-            for (col, cell) in row_data.iter().enumerate() {
-                if cell != crate::tile_rowstore::NULL_SENTINEL && cell.to_lowercase().contains(&query_lower) {
-                    results.find_matches.push(FindMatch {
-                        row: row_idx,
+        // Column by column, then back into reading order
+        let mut data_matches = Vec::new();
+        for col in 0..tile_store.ncols {
+            let cells = tile_store.get_column(col, start_row..end_row).unwrap_or_default();
+            for (offset, cell) in cells.iter().enumerate() {
+                if cell != crate::tile_rowstore::NULL_SENTINEL
+                    && crate::cell_value::display_text(cell).to_lowercase().contains(&query_lower)
+                {
+                    data_matches.push(FindMatch {
+                        row: start_row + offset,
                         col: col + 1,
                         is_header: false,
                     });
-                    // Still limit total matches to prevent memory issues
-                    if results.find_matches.len() >= 500 {
-                        return;
-                    }
                 }
             }
         }
+        data_matches.sort_by_key(|m| (m.row, m.col));
+        // Still limit total matches to prevent memory issues
+        data_matches.truncate(500usize.saturating_sub(results.find_matches.len()));
+        results.find_matches.extend(data_matches);
     }
 }
 
@@ -517,7 +519,7 @@ fn jump_to_find_match(results: &mut Results, idx: usize) {
         tab.cursor_row = m.row;
         tab.cursor_col = m.col;
         
-        if let ResultsContent::Table { headers: _, tile_store } = &tab.content {
+        if let ResultsContent::Table { tile_store, .. } = &tab.content {
             // Center the match vertically
             if results.max_rows > 0 {
                 let center_row = results.max_rows / 2;
@@ -655,7 +657,7 @@ pub fn handle_key(results: &mut Results, key: KeyEvent) {
     let tab = &mut results.tabs[results.tab_idx];
 
     match &mut tab.content {
-        ResultsContent::Table { headers, tile_store } => {
+        ResultsContent::Table { headers, tile_store, .. } => {
             let row_count = tile_store.nrows;
             let col_count = headers.len();
             let visible_cols = col_count + 1;
@@ -663,6 +665,7 @@ pub fn handle_key(results: &mut Results, key: KeyEvent) {
 
             let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
             let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+            let row_before = tab.cursor_row;
 
             /* ───────────────────── keyboard handling inside a Table tab ───────────────── */
             match key.code {
@@ -699,7 +702,7 @@ pub fn handle_key(results: &mut Results, key: KeyEvent) {
                         (0, total_rows)
                     };
 
-                    let data = tile_store.get_rows(first_row, rows_to_get).unwrap_or_default();
+                    let data = tile_store.get_full_rows(first_row, rows_to_get).unwrap_or_default();
 
                     /* shift row indices if we sliced */
                     let sel_shifted = if col_only && first_row > 0 {
@@ -843,8 +846,12 @@ pub fn handle_key(results: &mut Results, key: KeyEvent) {
             }   // ← closes match key.code
 
             tab.nudge_viewport(results.max_rows, results.max_cols, row_count, visible_cols);
+            // vertical moves steer tile read-ahead
+            if tab.scroll_direction == ScrollDirection::None && tab.cursor_row != row_before {
+                tab.scroll_direction = if tab.cursor_row > row_before { ScrollDirection::Down } else { ScrollDirection::Up };
+            }
         }
-        ResultsContent::Error { message, cursor, selection } => {
+        ResultsContent::Error { message, cursor, selection, .. } => {
             let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
             let shift = key.modifiers.contains(KeyModifiers::SHIFT);
             
@@ -915,8 +922,8 @@ pub fn handle_key(results: &mut Results, key: KeyEvent) {
                 _ => {}
             }
         }
-        ResultsContent::Pending => {}
-        ResultsContent::Info { .. } => {}
+        ResultsContent::Pending | ResultsContent::Queued { .. } => {}
+        ResultsContent::Info { .. } | ResultsContent::TimedOut { .. } => {}
     }
 }
 
@@ -927,7 +934,7 @@ pub fn handle_mouse(results: &mut Results, event: MouseEvent, area: UiRect) {
     let tab = &mut results.tabs[results.tab_idx];
     
     match &mut tab.content {
-        ResultsContent::Table { headers, tile_store } => {
+        ResultsContent::Table { headers, tile_store, .. } => {
             let row_count = tile_store.nrows;
             let col_count = headers.len();
             
@@ -1128,6 +1135,7 @@ pub fn handle_mouse(results: &mut Results, event: MouseEvent, area: UiRect) {
                     } else {
                         // Vertical scroll down
                         tab.view_row = (tab.view_row + 1).min(row_count.saturating_sub(results.max_rows));
+                        tab.scroll_direction = ScrollDirection::Down;
                     }
                 }
                 MouseEventKind::ScrollUp => {
@@ -1137,6 +1145,7 @@ pub fn handle_mouse(results: &mut Results, event: MouseEvent, area: UiRect) {
                     } else {
                         // Vertical scroll up
                         tab.view_row = tab.view_row.saturating_sub(1);
+                        tab.scroll_direction = ScrollDirection::Up;
                     }
                 }
                 MouseEventKind::ScrollRight => {
@@ -1155,7 +1164,7 @@ pub fn handle_mouse(results: &mut Results, event: MouseEvent, area: UiRect) {
                 _ => {}
             }
         }
-        ResultsContent::Error { message, cursor, selection } => {
+        ResultsContent::Error { message, cursor, selection, .. } => {
             // Account for tab bar (+1) and borders (+1)
             let content_area = UiRect { x: area.x, y: area.y + 1, width: area.width, height: area.height - 1 };
             let inner = content_area.inner(&tui::layout::Margin { horizontal: 1, vertical: 1 });
@@ -1205,8 +1214,8 @@ pub fn handle_mouse(results: &mut Results, event: MouseEvent, area: UiRect) {
                 }
             }
         }
-        ResultsContent::Pending => {}
-        ResultsContent::Info { .. } => {}
+        ResultsContent::Pending | ResultsContent::Queued { .. } => {}
+        ResultsContent::Info { .. } | ResultsContent::TimedOut { .. } => {}
     }
 }
 
@@ -1230,6 +1239,8 @@ pub fn render<B: Backend>(
         let label = match &t.content {
             ResultsContent::Table { .. } | ResultsContent::Pending | ResultsContent::Info { .. } => tabname,
             ResultsContent::Error { .. } => format!("Error {}", tabname),
+            ResultsContent::TimedOut { .. } => format!("Timed out {}", tabname),
+            ResultsContent::Queued { position, .. } => format!("Queued #{}", position),
         };
         if i == results.tab_idx {
             tabtitles.push(Spans::from(Span::styled(
@@ -1287,25 +1298,44 @@ pub fn render<B: Backend>(
     let tab = &mut results.tabs[results.tab_idx];
 
     let border_label = if tab.running {
+        let fetched = tab.rows_fetched
+            .map(|n| format!(", {} rows fetched", n))
+            .unwrap_or_default();
         if let Some(start) = tab.run_started {
             let hms = format_duration_hms(start.elapsed());
-            format!("{} (running: {})", tab.query_context, hms)
+            format!("{} (running: {}{})", tab.query_context, hms, fetched)
         } else {
-            format!("{} (running)", tab.query_context)
+            format!("{} (running{})", tab.query_context, fetched)
         }
     } else if let Some(elapsed) = tab.elapsed {
         let hms = format_duration_hms(elapsed);
-        format!("{} ({})", tab.query_context, hms)
+        let truncated = match (&tab.truncated, &tab.content) {
+            (Some(t), ResultsContent::Table { tile_store, .. }) => {
+                let more = if *t == Truncation::Resumable { " – n: next, N: all" } else { "" };
+                format!(", first {} rows{}", tile_store.nrows, more)
+            }
+            _ => String::new(),
+        };
+        format!("{} ({}{})", tab.query_context, hms, truncated)
     } else {
         tab.query_context.clone()
     };
+    let border_label = match &tab.query_id {
+        Some(id) if !tab.running => format!("{} – query ID {}", border_label, id),
+        _ => border_label,
+    };
 
     match &mut tab.content {
-        ResultsContent::Table { headers, tile_store } => {
+        ResultsContent::Table { headers, column_types, tile_store } => {
             // ---------- early-out if the result-set is empty ----------
             let total_rows = tile_store.nrows;
             if headers.is_empty() || total_rows == 0 {
-                let p = Paragraph::new("No rows returned (this statement did not produce a table)")
+                let msg = if tile_store.is_live() {
+                    "Fetching rows…"
+                } else {
+                    "No rows returned (this statement did not produce a table)"
+                };
+                let p = Paragraph::new(msg)
                     .block(
                     Block::default()
                         .title(Span::styled(
@@ -1340,7 +1370,7 @@ pub fn render<B: Backend>(
             let visible_rows  = tile_store
                 .get_rows(view_row, results.max_rows)
                 .unwrap_or_default();
-            tile_store.prefetch_for_view(view_row, results.max_rows);
+            tile_store.prefetch_for_view(view_row, results.max_rows, tab.scroll_direction.read_ahead());
 
             /* ---- 3️⃣  Calculate column widths if needed ---- */
             if tab.column_widths_cache.is_none() {
@@ -1516,7 +1546,7 @@ pub fn render<B: Backend>(
                     } else if row_offset < visible_rows.len() && col_idx - 1 < visible_rows[row_offset].len() {
                         // Data column
                         let cell_value = &visible_rows[row_offset][col_idx - 1];
-                        let mut cell_text = cell_value.replace('\n', " ");
+                        let mut cell_text = crate::cell_value::display_text(cell_value).replace('\n', " ");
                         
                         // Check if this cell is a find match
                         let is_find_match = if results.find_active && !results.find_matches.is_empty() {
@@ -1560,7 +1590,16 @@ pub fn render<B: Backend>(
                             visible_text
                         };
                         
-                        let padded = format!("{:width$}", display_text, width = visible_width as usize);
+                        // Numbers are right-aligned (keeping one space before the next column)
+                        let right_align = !is_null
+                            && skip_chars == 0
+                            && display_text.len() < visible_width as usize
+                            && column_types.get(col_idx - 1).map_or(false, |t| t.is_numeric());
+                        let padded = if right_align {
+                            format!("{:>width$} ", display_text, width = visible_width as usize - 1)
+                        } else {
+                            format!("{:width$}", display_text, width = visible_width as usize)
+                        };
                         
                         let base_style = if is_null {
                             Style::default().fg(k::STEEL_VIOLET)
@@ -1643,7 +1682,7 @@ pub fn render<B: Backend>(
             }
         }
 
-        ResultsContent::Error { message, cursor, selection } => {
+        ResultsContent::Error { message, cursor, selection, .. } => {
             // Calculate available width for wrapping
             let wrap_width = area.width.saturating_sub(2) as usize;
             // Use the same wrapping logic as mouse handling
@@ -1724,6 +1763,35 @@ pub fn render<B: Backend>(
                 },
             );
         }
+        ResultsContent::TimedOut { timeout } => {
+            let message = format!(
+                "Timed out: cancelled after the {} statement timeout.\n\
+                 Raise statement_timeout in Frost.toml or add a \"--@timeout 10m\" hint to this statement.",
+                format_duration_hms(*timeout),
+            );
+            let p = Paragraph::new(message)
+                .block(Block::default()
+                    .title(Span::styled(
+                        border_label,
+                        STYLE::results_border_focus()  // Always use active color for title text
+                    ))
+                    .borders(Borders::ALL)
+                    .border_style(if results.focus {
+                        STYLE::results_border_focus()
+                    } else {
+                        STYLE::results_border()
+                    }))
+                .style(STYLE::error_fg());
+            f.render_widget(
+                p,
+                UiRect {
+                    x: area.x,
+                    y: area.y + 1,
+                    width: area.width,
+                    height: area.height - 1,
+                },
+            );
+        }
         ResultsContent::Pending => {
             let p = Paragraph::new("")
                 .block(Block::default()
@@ -1747,6 +1815,35 @@ pub fn render<B: Backend>(
                 },
             );
         }
+        ResultsContent::Queued { position, sql } => {
+            let message = format!(
+                "Queued #{} – starts when the running query is done.\n\
+                 <  >  move earlier / later     x  remove from the queue\n\n{}",
+                position, sql,
+            );
+            let p = Paragraph::new(message)
+                .block(Block::default()
+                    .title(Span::styled(
+                        border_label,
+                        STYLE::results_border_focus()  // Always use active color for title text
+                    ))
+                    .borders(Borders::ALL)
+                    .border_style(if results.focus {
+                        STYLE::results_border_focus()
+                    } else {
+                        STYLE::results_border()
+                    }))
+                .style(STYLE::info_fg());
+            f.render_widget(
+                p,
+                UiRect {
+                    x: area.x,
+                    y: area.y + 1,
+                    width: area.width,
+                    height: area.height - 1,
+                },
+            );
+        }
     }
 }
 
@@ -1778,120 +1875,121 @@ pub fn cell_in_selection(row: usize, col: usize, sel: &ResultSelection) -> bool
 }
 
 // ────────────────────────────────────────────────────────────────
-//  Fast, sample-based summary of the *current* selection
+//  Summary of the *current* selection, scanned column by column
 // ────────────────────────────────────────────────────────────────
 pub fn compute_selection_summary(
     sel: &ResultSelection,
     headers: &[String],
+    column_types: &[crate::column_type::ColumnType],
     tile_store: &mut crate::tile_rowstore::TileRowStore,
 ) -> Option<(String /*stats*/, Option<String> /*warning*/)> {
-    /// Hard-cap: analyse at most this many **rows**
-    const MAX_SUMMARY_ROWS: usize = 10_000;
-
-    if matches!(sel.kind, SelectionKind::None) {
-        return None;                           // nothing selected
-    }
+    /// Distinct values tracked for mode/uniq; past this uniq shows "N+"
+    const MAX_DISTINCT_VALUES: usize = 100_000;
+    /// Rows fetched per `get_column` call
+    const SCAN_CHUNK: usize = 16 * crate::tile_rowstore::TILE_SIZE;
 
     let nrows = tile_store.nrows;
     let ncols = headers.len();
 
-    /* helper: iterator over every logical (row,col) pair in the selection */
-    fn cells<'a>(
-        sel: &'a ResultSelection,
-        nrows: usize,
-        ncols: usize,
-    ) -> Box<dyn Iterator<Item = (usize, usize)> + 'a> {
-        use SelectionKind::*;
-        match &sel.kind {
-            FullRowSet { anchor, cursor } => {
-                let (s, e) = (*anchor.min(cursor), *anchor.max(cursor));
-                Box::new((s..=e).flat_map(move |r| (1..=ncols).map(move |c| (r, c))))
-            }
-            FullRowVec(rows) => Box::new(
-                rows.clone()
-                    .into_iter()
-                    .flat_map(move |r| (1..=ncols).map(move |c| (r, c))),
-            ),
-            FullColSet { anchor, cursor } => {
-                let (s, e) = (*anchor.min(cursor), *anchor.max(cursor));
-                Box::new((0..nrows).flat_map(move |r| (s..=e).map(move |c| (r, c))))
-            }
-            FullColVec(cols) => Box::new(
-                cols.clone()
-                    .into_iter()
-                    .flat_map(move |c| (0..nrows).map(move |r| (r, c))),
-            ),
-            Rect => {
-                if let (Some(a), Some(b)) = (sel.anchor, sel.cursor) {
-                    let r0 = a.0.min(b.0);
-                    let r1 = a.0.max(b.0);
-                    let c0 = a.1.min(b.1).max(1);   // skip "#" index col
-                    let c1 = a.1.max(b.1);
-                    Box::new((r0..=r1).flat_map(move |r| (c0..=c1).map(move |c| (r, c))))
-                } else {
-                    Box::new(std::iter::empty())
+    /* rows (as ranges) and 1-based columns the selection covers */
+    let (row_ranges, cols): (Vec<std::ops::Range<usize>>, Vec<usize>) = match &sel.kind {
+        SelectionKind::FullRowSet { anchor, cursor } => {
+            (vec![*anchor.min(cursor)..*anchor.max(cursor) + 1], (1..=ncols).collect())
+        }
+        SelectionKind::FullRowVec(rows) => {
+            let mut rows = rows.clone();
+            rows.sort_unstable();
+            rows.dedup();
+            let mut ranges: Vec<std::ops::Range<usize>> = Vec::new();
+            for r in rows {
+                match ranges.last_mut() {
+                    Some(last) if last.end == r => last.end += 1,
+                    _ => ranges.push(r..r + 1),
                 }
             }
-            None => Box::new(std::iter::empty()),
+            (ranges, (1..=ncols).collect())
+        }
+        SelectionKind::FullColSet { anchor, cursor } => {
+            (vec![0..nrows], (*anchor.min(cursor)..=*anchor.max(cursor)).collect())
+        }
+        SelectionKind::FullColVec(cols) => {
+            let mut cols = cols.clone();
+            cols.sort_unstable();
+            cols.dedup();
+            (vec![0..nrows], cols)
+        }
+        SelectionKind::Rect => {
+            let (a, b) = sel.anchor.zip(sel.cursor)?;
+            let c0 = a.1.min(b.1).max(1);   // skip "#" index col
+            (vec![a.0.min(b.0)..a.0.max(b.0) + 1], (c0..=a.1.max(b.1)).collect())
         }
+        SelectionKind::None => return None, // nothing selected
+    };
+    let row_ranges: Vec<_> = row_ranges.into_iter()
+        .map(|r| r.start.min(nrows)..r.end.min(nrows))
+        .filter(|r| !r.is_empty())
+        .collect();
+    let cols: Vec<usize> = cols.into_iter().filter(|&c| c >= 1 && c <= ncols).collect();
+    let rows: usize = row_ranges.iter().map(|r| r.len()).sum();
+    if rows == 0 || cols.is_empty() {
+        return None;
     }
 
     // ── accumulators ─────────────────────────────────────────────
-    use std::collections::HashSet;
-
-    let mut rows_seen: HashSet<usize> = HashSet::with_capacity(MAX_SUMMARY_ROWS);
     let mut total_cells   = 0usize;
     let mut null_cells    = 0usize;
     let mut numeric_sum   = 0f64;
     let mut numeric_cnt   = 0usize;
-    let mut freq:    HashMap<String, usize> = HashMap::new();
-    let mut uniques: HashSet<String>        = HashSet::new();
+    let mut freq: HashMap<String, usize> = HashMap::new();
+    let mut freq_capped = false;
     let mut whitespace = false;
 
-    // ── main walk over the selection ─────────────────────────────
-    for (r, c) in cells(sel, nrows, ncols) {
-        // stop once we've processed the desired number of **rows**
-        if rows_seen.len() >= MAX_SUMMARY_ROWS && !rows_seen.contains(&r) {
-            break;
-        }
-        rows_seen.insert(r);
-
-        // fetch the single row we need
-        let row_vec = tile_store.get_rows(r, 1).ok()?.pop()?;
-        let idx = (c - 1) as usize;            // 1-based → 0-based
-        if idx >= row_vec.len() { continue; }
-        let cell = row_vec[idx].clone();
-        total_cells += 1;
-
-        // true NULL?
-        if cell == crate::tile_rowstore::NULL_SENTINEL
-            || cell.eq_ignore_ascii_case("null")
-        {
-            null_cells += 1;
-            continue;
-        }
-
-        let trimmed = cell.trim();
+    // ── walk each selected column; only that column is decoded ───
+    for &c in &cols {
+        let idx = c - 1;                       // 1-based → 0-based
+        // only numeric columns contribute to sum/avg (ids in VARCHARs don't)
+        let col_type = column_types.get(idx).copied().unwrap_or(crate::column_type::ColumnType::Text);
+
+        for range in &row_ranges {
+            let mut start = range.start;
+            while start < range.end {
+                let end = (start + SCAN_CHUNK).min(range.end);
+                let cells = tile_store.get_column(idx, start..end).ok()?;
+                start = end;
+
+                for cell in &cells {
+                    total_cells += 1;
+
+                    // true NULL?
+                    if cell == crate::tile_rowstore::NULL_SENTINEL
+                        || cell.eq_ignore_ascii_case("null")
+                    {
+                        null_cells += 1;
+                        continue;
+                    }
 
-        if let Ok(n) = trimmed.parse::<f64>() {
-            numeric_sum += n;
-            numeric_cnt += 1;
-        }
+                    let trimmed = cell.trim();
+                    if col_type.is_numeric() {
+                        if let Some(n) = col_type.parse(trimmed).as_f64() {
+                            numeric_sum += n;
+                            numeric_cnt += 1;
+                        }
+                    }
 
-        whitespace |= trimmed.len() != cell.len();
+                    whitespace |= trimmed.len() != cell.len();
 
-        if uniques.len() < MAX_SUMMARY_ROWS {
-            uniques.insert(trimmed.to_owned());
-        }
-        if freq.len() < MAX_SUMMARY_ROWS {
-            *freq.entry(trimmed.to_owned()).or_insert(0) += 1;
+                    if let Some(n) = freq.get_mut(trimmed) {
+                        *n += 1;
+                    } else if freq.len() < MAX_DISTINCT_VALUES {
+                        freq.insert(trimmed.to_owned(), 1);
+                    } else {
+                        freq_capped = true;
+                    }
+                }
+            }
         }
     }
 
-    if rows_seen.is_empty() {
-        return None;
-    }
-
     // ── compute summary numbers ─────────────────────────────────
     let mode_raw = freq
         .iter()
@@ -1909,14 +2007,15 @@ pub fn compute_selection_summary(
 
     let null_pct = (null_cells as f64) * 100.0 / (total_cells as f64);
 
-    // main stats line (count now == distinct rows scanned)
+    // main stats line (rows == distinct rows in the selection)
     let stats = format!(
-        "sum: {sum}   avg: {avg}   mode: {mode}   rows: {rows}   uniq: {uniq}   null: {null:.1}%",
+        "sum: {sum}   avg: {avg}   mode: {mode}   rows: {rows}   uniq: {uniq}{more}   null: {null:.1}%",
         sum  = sum_str,
         avg  = avg_str,
         mode = mode,
-        rows = rows_seen.len(),
-        uniq = uniques.len(),
+        rows = rows,
+        uniq = freq.len(),
+        more = if freq_capped { "+" } else { "" },
         null = null_pct,
     );
 
@@ -0,0 +1,148 @@
+//! `${env:NAME}`, `${file:path}` and `${prompt:Label}` placeholders in
+//! connection strings, so Frost.toml doesn't have to hold passwords.
+//!
+//! Placeholders are expanded right before connecting. `prompt:` values are
+//! asked for once per process (a masked dialog in the TUI, the environment or
+//! stdin in batch mode) and reach the crawler as `FROST_PROMPT_<LABEL>`
+//! environment variables.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
+
+/// Values typed in for `${prompt:…}` placeholders, by label
+static PROMPTED: Lazy<Mutex<HashMap<String, String>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+enum Source<'a> {
+    Env(&'a str),
+    File(&'a str),
+    Prompt(&'a str),
+}
+
+/// Placeholders of `text` with their byte ranges. Unknown kinds like
+/// `${foo:bar}` are not placeholders and stay as written.
+fn placeholders(text: &str) -> Vec<(std::ops::Range<usize>, Source<'_>)> {
+    let mut found = Vec::new();
+    let mut from = 0;
+    while let Some(start) = text[from..].find("${").map(|i| from + i) {
+        let Some(end) = text[start..].find('}').map(|i| start + i) else { break };
+        let inner = &text[start + 2..end];
+        let source = inner.split_once(':').and_then(|(kind, arg)| {
+            let arg = arg.trim();
+            match kind.trim().to_ascii_lowercase().as_str() {
+                "env" => Some(Source::Env(arg)),
+                "file" => Some(Source::File(arg)),
+                "prompt" => Some(Source::Prompt(arg)),
+                _ => None,
+            }
+        });
+        if let Some(source) = source {
+            found.push((start..end + 1, source));
+        }
+        from = end + 1;
+    }
+    found
+}
+
+/// Environment variable that can hold the value of `${prompt:label}`
+pub fn prompt_env_var(label: &str) -> String {
+    let name: String = label
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect();
+    format!("FROST_PROMPT_{}", name)
+}
+
+fn prompted_value(label: &str) -> Option<String> {
+    if let Some(value) = PROMPTED.lock().unwrap().get(label) {
+        return Some(value.clone());
+    }
+    std::env::var(prompt_env_var(label)).ok()
+}
+
+/// `${prompt:…}` labels of `conn_str` that don't have a value yet
+pub fn missing_prompts(conn_str: &str) -> Vec<String> {
+    let mut labels: Vec<String> = Vec::new();
+    for (_, source) in placeholders(conn_str) {
+        if let Source::Prompt(label) = source {
+            if prompted_value(label).is_none() && !labels.iter().any(|l| l == label) {
+                labels.push(label.to_string());
+            }
+        }
+    }
+    labels
+}
+
+/// Remember what was typed for `${prompt:label}`
+pub fn set_prompt(label: &str, value: String) {
+    PROMPTED.lock().unwrap().insert(label.to_string(), value);
+}
+
+/// Prompted values as `FROST_PROMPT_*` variables, for child processes
+pub fn prompt_env() -> Vec<(String, String)> {
+    PROMPTED
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(label, value)| (prompt_env_var(label), value.clone()))
+        .collect()
+}
+
+/// `~/…` relative to the home directory
+fn expand_home(path: &str) -> std::path::PathBuf {
+    match path.strip_prefix("~/").or_else(|| path.strip_prefix("~\\")) {
+        Some(rest) => directories::UserDirs::new()
+            .map(|dirs| dirs.home_dir().join(rest))
+            .unwrap_or_else(|| rest.into()),
+        None => path.into(),
+    }
+}
+
+/// A connection string with its placeholders filled in
+pub struct Expanded {
+    pub text: String,
+    secrets: Vec<String>,
+}
+
+impl Expanded {
+    /// `message` with every substituted value masked, for errors that might
+    /// quote the connection string
+    pub fn redact(&self, message: &str) -> String {
+        let mut out = message.to_string();
+        let mut secrets: Vec<&String> = self.secrets.iter().filter(|s| !s.is_empty()).collect();
+        secrets.sort_by_key(|s| std::cmp::Reverse(s.len()));
+        for secret in secrets {
+            out = out.replace(secret.as_str(), "****");
+        }
+        out
+    }
+}
+
+/// Replace the placeholders of `conn_str`. Errors name the placeholder,
+/// never its value.
+pub fn expand(conn_str: &str) -> Result<Expanded> {
+    let mut text = String::with_capacity(conn_str.len());
+    let mut secrets = Vec::new();
+    let mut last = 0;
+    for (range, source) in placeholders(conn_str) {
+        let placeholder = &conn_str[range.clone()];
+        let value = match source {
+            Source::Env(name) => std::env::var(name)
+                .map_err(|_| anyhow!("Environment variable {} for {} is not set", name, placeholder))?,
+            Source::File(path) => std::fs::read_to_string(expand_home(path))
+                .map_err(|e| anyhow!("Could not read {}: {}", placeholder, e))?
+                .trim_end_matches(['\r', '\n'])
+                .to_string(),
+            Source::Prompt(label) => prompted_value(label)
+                .ok_or_else(|| anyhow!("No value entered for {}", placeholder))?,
+        };
+        text.push_str(&conn_str[last..range.start]);
+        text.push_str(&value);
+        secrets.push(value);
+        last = range.end;
+    }
+    text.push_str(&conn_str[last..]);
+    Ok(Expanded { text, secrets })
+}
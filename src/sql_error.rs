@@ -0,0 +1,150 @@
+//! Error text from the backends broken into its parts.
+//!
+//! The worker reports failures as `Execution Error: State: 42000, Native
+//! error: 1003, Message: SQL compilation error: …`. The error tab shows the
+//! SQLSTATE, native code and message separately, and Snowflake's
+//! `line N at position M` is mapped back onto the statement in the editor.
+
+use crate::bind_vars::Substitution;
+
+/// A parsed error message
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SqlError {
+    pub sqlstate: Option<String>,
+    pub native_code: Option<i64>,
+    pub message: String,
+    /// `line N at position M`: 1-based line, 0-based position within the line
+    pub position: Option<(usize, usize)>,
+}
+
+impl SqlError {
+    pub fn parse(raw: &str) -> Self {
+        let sqlstate = raw
+            .split("State: ")
+            .nth(1)
+            .and_then(|rest| rest.get(..5))
+            .filter(|s| s.bytes().all(|b| b.is_ascii_alphanumeric()))
+            .map(str::to_string);
+        let native_code = raw.split(", Native error: ").nth(1).and_then(|rest| {
+            let end = rest
+                .char_indices()
+                .find(|&(i, c)| !(c.is_ascii_digit() || (i == 0 && c == '-')))
+                .map(|(i, _)| i)
+                .unwrap_or(rest.len());
+            rest[..end].parse().ok()
+        });
+        let message = match raw.split_once(", Message: ") {
+            Some((_, msg)) => strip_driver_tags(msg),
+            // no ODBC-style header; drop the "Execution Error: " kind of prefix
+            None => raw.split_once("Error: ").map(|(_, m)| m).unwrap_or(raw),
+        }
+        .trim()
+        .to_string();
+        let position = find_position(&message);
+        Self { sqlstate, native_code, message, position }
+    }
+
+    /// What the error tab shows
+    pub fn display_text(&self, location: Option<&ErrorLocation>) -> String {
+        let mut out = self.message.clone();
+        let mut codes = Vec::new();
+        if let Some(state) = &self.sqlstate {
+            codes.push(format!("SQLSTATE {}", state));
+        }
+        if let Some(code) = self.native_code {
+            codes.push(format!("Native error {}", code));
+        }
+        if !codes.is_empty() {
+            out.push_str("\n\n");
+            out.push_str(&codes.join("   "));
+        }
+        if let Some(loc) = location {
+            out.push_str(&format!(
+                "\nLine {}, position {} of the statement – press e to jump to it in the editor",
+                loc.line, loc.column
+            ));
+        }
+        out
+    }
+}
+
+/// `[Snowflake][ODBC] (11560) ` style prefixes in front of the real message
+fn strip_driver_tags(msg: &str) -> &str {
+    let mut s = msg.trim_start();
+    while s.starts_with('[') {
+        match s.find(']') {
+            Some(end) => s = s[end + 1..].trim_start(),
+            None => break,
+        }
+    }
+    if let Some(rest) = s.strip_prefix('(') {
+        if let Some((code, after)) = rest.split_once(')') {
+            if !code.is_empty() && code.bytes().all(|b| b.is_ascii_digit()) {
+                s = after.trim_start();
+            }
+        }
+    }
+    s
+}
+
+/// The first `line N at position M` (also `error line N at position M`)
+fn find_position(message: &str) -> Option<(usize, usize)> {
+    let lower = message.to_ascii_lowercase();
+    lower.match_indices("line ").find_map(|(i, _)| {
+        let rest = &lower[i + 5..];
+        let (line, rest) = leading_number(rest)?;
+        let rest = rest.strip_prefix(" at position ")?;
+        let (pos, _) = leading_number(rest)?;
+        Some((line, pos))
+    })
+}
+
+fn leading_number(s: &str) -> Option<(usize, &str)> {
+    let end = s.bytes().position(|b| !b.is_ascii_digit()).unwrap_or(s.len());
+    Some((s[..end].parse().ok()?, &s[end..]))
+}
+
+/// Where a statement started in the editor buffer when it was run
+#[derive(Debug, Clone)]
+pub struct StatementSource {
+    pub offset: usize,
+    pub text: String,
+    /// Bind placeholders replaced before it ran, to map positions back
+    pub binds: Vec<Substitution>,
+}
+
+/// An error position within a statement from the editor. `line` is 1-based,
+/// `column` 0-based in characters, both relative to the statement as written.
+#[derive(Debug, Clone)]
+pub struct ErrorLocation {
+    pub source: StatementSource,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl ErrorLocation {
+    /// Byte offset of the error in `buffer`, or `None` once the statement has
+    /// been edited away. A moved statement is found again by its text.
+    pub fn resolve(&self, buffer: &str) -> Option<usize> {
+        let text = self.source.text.as_str();
+        let start = if buffer.get(self.source.offset..).is_some_and(|b| b.starts_with(text)) {
+            self.source.offset
+        } else {
+            buffer
+                .match_indices(text)
+                .map(|(i, _)| i)
+                .min_by_key(|i| i.abs_diff(self.source.offset))?
+        };
+
+        // Past the last line means the error is at the very end
+        let mut line_start = 0;
+        for (n, line) in text.split('\n').enumerate() {
+            if n + 1 == self.line.max(1) {
+                let col = line.char_indices().nth(self.column).map(|(b, _)| b).unwrap_or(line.len());
+                return Some(start + line_start + col);
+            }
+            line_start += line.len() + 1;
+        }
+        Some(start + text.len())
+    }
+}
@@ -0,0 +1,292 @@
+//! Local SQLite backend (`sqlite:///path/to/file.db` or `sqlite::memory:`).
+//!
+//! Handy for offline prototyping and demos; results go through the same
+//! worker → `ResultsContent` path as Snowflake.
+
+use std::sync::{Arc, Mutex};
+
+use rusqlite::types::ValueRef;
+use rusqlite::{Connection, InterruptHandle, Row, Statement};
+
+use crate::cell_value::{build_cell, ValueOptions, ValueSpill};
+use crate::column_type::ColumnType;
+use crate::query_backend::{
+    BackendError, BackendResult, ColumnInfo, Execution, QueryBackend, QueryCanceller, RowStream,
+};
+
+/// Connection-string prefix that selects this backend
+pub const SQLITE_PREFIX: &str = "sqlite:";
+const SQLITE_MEMORY: &str = "sqlite::memory:";
+
+impl From<rusqlite::Error> for BackendError {
+    fn from(e: rusqlite::Error) -> Self {
+        // Mirror the ODBC diagnostic layout so the UI treats both the same way
+        match &e {
+            rusqlite::Error::SqliteFailure(err, msg) => {
+                let state = if err.code == rusqlite::ErrorCode::OperationInterrupted { "HY008" } else { "HY000" };
+                BackendError::new(format!(
+                    "State: {}, Native error: {}, Message: {}",
+                    state,
+                    err.extended_code,
+                    msg.clone().unwrap_or_else(|| err.to_string()),
+                ))
+            }
+            other => BackendError::new(format!("State: HY000, Native error: 0, Message: {}", other)),
+        }
+    }
+}
+
+/// Interrupts the running statement via `sqlite3_interrupt`.
+pub struct SqliteCanceller {
+    handle: Arc<Mutex<Option<InterruptHandle>>>,
+}
+
+impl QueryCanceller for SqliteCanceller {
+    fn cancel(&self) {
+        if let Some(h) = self.handle.lock().unwrap().as_ref() {
+            h.interrupt();
+        }
+    }
+}
+
+pub struct SqliteBackend {
+    target: String,
+    conn: Option<Connection>,
+    interrupt: Arc<Mutex<Option<InterruptHandle>>>,
+    manual: bool,  // keep a `BEGIN` open between commits
+    value_options: ValueOptions,
+}
+
+impl SqliteBackend {
+    /// `conn_str` is the full `sqlite:` connection string.
+    pub fn new(conn_str: &str) -> Self {
+        Self {
+            target: conn_str.trim().to_string(),
+            conn: None,
+            interrupt: Arc::new(Mutex::new(None)),
+            manual: false,
+            value_options: ValueOptions::default(),
+        }
+    }
+
+    /// Database file named by a `sqlite:` connection string, `None` for in-memory.
+    fn db_path(target: &str) -> Option<String> {
+        if target.eq_ignore_ascii_case(SQLITE_MEMORY) {
+            return None;
+        }
+        let rest = target[SQLITE_PREFIX.len()..].trim_start_matches("//");
+        // sqlite:///C:/data/x.db → C:/data/x.db
+        let rest = match rest.as_bytes() {
+            [b'/', drive, b':', ..] if drive.is_ascii_alphabetic() => &rest[1..],
+            _ => rest,
+        };
+        Some(rest.to_string())
+    }
+
+    /// SQLite has no autocommit switch; manual mode is an explicit `BEGIN`
+    /// that is reopened after every commit or rollback.
+    fn end_transaction(&mut self, verb: &str) -> BackendResult<()> {
+        let conn = self.conn.as_ref().ok_or_else(|| BackendError::new("Not connected"))?;
+        if !self.manual {
+            return Err(BackendError::new("Autocommit is on; there is no open transaction"));
+        }
+        if !conn.is_autocommit() {
+            conn.execute_batch(verb)?;
+        }
+        conn.execute_batch("BEGIN")?;
+        Ok(())
+    }
+}
+
+/// Whether `sql` can change rows (so `changes()` is meaningful for it)
+fn is_dml(sql: &str) -> bool {
+    let first = sql
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty() && !l.starts_with("--"))
+        .and_then(|l| l.split_whitespace().next())
+        .unwrap_or("")
+        .to_uppercase();
+    matches!(first.as_str(), "INSERT" | "UPDATE" | "DELETE" | "REPLACE" | "WITH")
+}
+
+fn value_to_string(v: ValueRef<'_>, options: ValueOptions, spill: &ValueSpill) -> BackendResult<Option<String>> {
+    let cell = match v {
+        ValueRef::Null => return Ok(None),
+        ValueRef::Integer(i) => return Ok(Some(i.to_string())),
+        ValueRef::Real(f) => return Ok(Some(f.to_string())),
+        ValueRef::Text(t) => build_cell(options, spill, false, t),
+        ValueRef::Blob(b) => build_cell(options, spill, true, b),
+    };
+    cell.map(Some).map_err(|e| BackendError::new(format!("Could not store a large value: {}", e)))
+}
+
+fn row_to_strings(row: &Row<'_>, ncols: usize, options: ValueOptions, spill: &ValueSpill) -> BackendResult<Vec<Option<String>>> {
+    (0..ncols).map(|idx| value_to_string(row.get_ref(idx)?, options, spill)).collect()
+}
+
+fn storage_class_type(v: ValueRef<'_>) -> ColumnType {
+    match v {
+        ValueRef::Integer(_) => ColumnType::Integer,
+        ValueRef::Real(_) => ColumnType::Float,
+        ValueRef::Blob(_) => ColumnType::Binary,
+        ValueRef::Null | ValueRef::Text(_) => ColumnType::Text,
+    }
+}
+
+impl QueryBackend for SqliteBackend {
+    fn connection_string(&self) -> &str {
+        &self.target
+    }
+
+    fn connect(&mut self, conn_str: &str) -> BackendResult<()> {
+        let conn = match Self::db_path(conn_str.trim()) {
+            Some(path) => Connection::open(path)?,
+            None => Connection::open_in_memory()?,
+        };
+        *self.interrupt.lock().unwrap() = Some(conn.get_interrupt_handle());
+        self.conn = Some(conn);
+        self.manual = false;
+        Ok(())
+    }
+
+    fn execute<'s>(&'s mut self, sql: &str) -> BackendResult<Execution<'s>> {
+        let conn = self.conn.as_ref().ok_or_else(|| BackendError::new("Not connected"))?;
+        let mut stmt = conn.prepare(sql.trim().trim_end_matches(';'))?;
+
+        if stmt.column_count() == 0 {
+            let changed = stmt.raw_execute()?;
+            let affected_rows = Some(if is_dml(sql) { changed as i64 } else { 0 });
+            return Ok(Execution::NoRows { affected_rows, query_id: None });
+        }
+
+        // Declared types where there are any; expressions get typed from the
+        // storage class of their first non-NULL value below.
+        let mut columns: Vec<ColumnInfo> = stmt
+            .columns()
+            .into_iter()
+            .map(|col| ColumnInfo {
+                name: col.name().to_string(),
+                col_type: col.decl_type().map(ColumnType::from_type_name).unwrap_or(ColumnType::Text),
+            })
+            .collect();
+        let declared: Vec<bool> = stmt.columns().iter().map(|c| c.decl_type().is_some()).collect();
+
+        // Rows are stepped as the worker asks for them. Expressions get their
+        // type from the storage class of the first row, which is read here.
+        let options = self.value_options;
+        let spill = Arc::new(ValueSpill::default());
+        let mut done = false;
+        let first = step_once(&mut stmt, &mut done, |row| {
+            for (idx, col) in columns.iter_mut().enumerate() {
+                let value = row.get_ref(idx)?;
+                if !declared[idx] && value != ValueRef::Null {
+                    col.col_type = storage_class_type(value);
+                }
+            }
+            row_to_strings(row, columns.len(), options, &spill)
+        })?;
+
+        Ok(Execution::Rows(Box::new(SqliteRows { stmt, done, columns, first, options, spill })))
+    }
+
+    fn canceller(&self) -> Arc<dyn QueryCanceller> {
+        Arc::new(SqliteCanceller { handle: Arc::clone(&self.interrupt) })
+    }
+
+    fn set_manual_transactions(&mut self, manual: bool) -> BackendResult<()> {
+        let conn = self.conn.as_ref().ok_or_else(|| BackendError::new("Not connected"))?;
+        if manual && conn.is_autocommit() {
+            conn.execute_batch("BEGIN")?;
+        } else if !manual && !conn.is_autocommit() {
+            conn.execute_batch("COMMIT")?;
+        }
+        self.manual = manual;
+        Ok(())
+    }
+
+    fn commit(&mut self) -> BackendResult<()> {
+        self.end_transaction("COMMIT")
+    }
+
+    fn rollback(&mut self) -> BackendResult<()> {
+        self.end_transaction("ROLLBACK")
+    }
+
+    /// Each in-memory connection is its own empty database
+    fn new_connection(&self) -> Option<Box<dyn QueryBackend>> {
+        Self::db_path(&self.target)?;
+        let mut backend = SqliteBackend::new(&self.target);
+        backend.value_options = self.value_options;
+        Some(Box::new(backend))
+    }
+
+    fn set_value_options(&mut self, options: ValueOptions) {
+        self.value_options = options;
+    }
+}
+
+/// Step `stmt` to its next row and read it with `read`; `None` once the rows
+/// are done (`done` is then set, as stepping again would restart the query).
+/// A `Rows` resets its statement when dropped, so the one used for a row is
+/// forgotten instead (it only borrows the statement) and the cursor stays
+/// where it is until the next call.
+fn step_once<T>(
+    stmt: &mut Statement<'_>,
+    done: &mut bool,
+    read: impl FnOnce(&Row<'_>) -> BackendResult<T>,
+) -> BackendResult<Option<T>> {
+    if *done {
+        return Ok(None);
+    }
+    let mut rows = stmt.raw_query();
+    let value = match rows.next() {
+        Ok(Some(row)) => read(row),
+        Ok(None) => {
+            *done = true;
+            return Ok(None);
+        }
+        Err(e) => Err(e.into()),
+    };
+    match value {
+        Ok(value) => {
+            std::mem::forget(rows);
+            Ok(Some(value))
+        }
+        Err(e) => {
+            *done = true;
+            Err(e)
+        }
+    }
+}
+
+/// A cursor over a statement it owns, stepped one row per `next_row`
+struct SqliteRows<'s> {
+    stmt: Statement<'s>,
+    /// Set once the statement has run out of rows or failed
+    done: bool,
+    columns: Vec<ColumnInfo>,
+    /// Read while typing the columns, handed out first
+    first: Option<Vec<Option<String>>>,
+    options: ValueOptions,
+    spill: Arc<ValueSpill>,
+}
+
+impl RowStream for SqliteRows<'_> {
+    fn columns(&self) -> &[ColumnInfo] {
+        &self.columns
+    }
+
+    fn next_row(&mut self) -> BackendResult<Option<Vec<Option<String>>>> {
+        if let Some(row) = self.first.take() {
+            return Ok(Some(row));
+        }
+        step_once(&mut self.stmt, &mut self.done, |row| {
+            row_to_strings(row, self.columns.len(), self.options, &self.spill)
+        })
+    }
+
+    fn value_spill(&self) -> Option<Arc<ValueSpill>> {
+        Some(Arc::clone(&self.spill))
+    }
+}
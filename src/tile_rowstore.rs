@@ -1,11 +1,25 @@
 use std::{
+    collections::{HashMap, HashSet},
     fs::{File, OpenOptions},
     io::{self, Read, Write, Seek, SeekFrom,BufWriter},
-    sync::Arc,
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex,
+    },
+    thread,
 };
 use tempfile::NamedTempFile;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use lru::LruCache;
+use once_cell::sync::Lazy;
+use crc32fast::hash as crc32;
+use flate2::{read::DeflateDecoder, write::DeflateEncoder, Compression};
+use chrono::{Datelike, NaiveDate, Timelike};
+use crate::cell_value::{self, BinaryFormat, LargeValue, ValueSpill};
+use crate::column_type::{parse_bool, parse_timestamp, ColumnType, DATE_FORMAT, TIMESTAMP_FORMAT};
 
 
 pub const NULL_SENTINEL: &str = "<Frost-NULL>";
@@ -13,172 +27,621 @@ pub const NULL_SENTINEL: &str = "<Frost-NULL>";
 /// Number of rows per tile (can be made configurable)
 pub const TILE_SIZE: usize = 1_000;
 
+/// Default for `tile_cache_mb`: decoded tiles all result tabs keep in memory
+pub const DEFAULT_CACHE_MB: usize = 64;
+
+/// Decoded tiles of every open store, within one budget (see [`set_cache_budget`])
+static CACHE: Lazy<Mutex<TileCache>> = Lazy::new(|| Mutex::new(TileCache::new(DEFAULT_CACHE_MB * 1024 * 1024)));
+
+/// Keys the stores' entries in `CACHE`
+static NEXT_STORE_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Tiles read ahead in the direction the view is moving
+const READ_AHEAD_TILES: usize = 4;
+
+/// Prefetch requests in flight per store; later ones are dropped until these land
+const MAX_PENDING_PREFETCH: usize = 8;
+
+// Tile file layout (little endian), version 5:
+//   header  "SNTR", version u16, flags u16, tile size u32, column count u32,
+//           one type tag per column
+//   tiles   row count u32, column count u32, then per column: stored length
+//           u32, data length u32, flags u8, CRC32 u32 of the stored bytes;
+//           then each column's block (an encoding byte and its cells,
+//           deflated if COL_DEFLATE is set), so one column can be read alone
+//   footer  per tile: offset u64, length u64, rows u32, CRC32 u32;
+//           then row count u64, tile count u64, footer offset u64,
+//           CRC32 of the footer u32, "SNTX"
+// Live views read tiles through the writer's in-memory index; the footer is
+// only written when the writer finishes.
+
 /// Magic header for file sanity
 const MAGIC: &[u8; 4] = b"SNTR";
 
+/// Bumped whenever the layout above changes
+const FORMAT_VERSION: u16 = 5;
+
+/// Last bytes of a finished file
+const FOOTER_MAGIC: &[u8; 4] = b"SNTX";
+
+/// Bytes of one footer index entry
+const INDEX_ENTRY_LEN: u64 = 8 + 8 + 4 + 4;
+
+/// Bytes of the fixed end of the footer
+const FOOTER_TAIL_LEN: u64 = 8 + 8 + 8 + 4 + 4;
+
+/// Bytes of one entry in a tile's column directory
+const COLUMN_DIR_LEN: usize = 4 + 4 + 1 + 4;
+
+/// Per-cell encoding tags. Typed encodings are only used when they reproduce
+/// the original text exactly; anything else is stored as `CELL_TEXT`.
+const CELL_NULL: u8 = 0;
+const CELL_TEXT: u8 = 1;
+const CELL_INT: u8 = 2;
+const CELL_FLOAT: u8 = 3;
+const CELL_BOOL: u8 = 4;
+const CELL_DATE: u8 = 5;
+const CELL_TIMESTAMP: u8 = 6;
+/// A value in the spill file: offset u64, length u64, kind u8 (see
+/// `LARGE_KINDS`), then its preview like a text cell
+const CELL_LARGE: u8 = 7;
+
+/// How a spilled value is shown, by its kind byte: text, hex or base64
+const LARGE_KINDS: [Option<BinaryFormat>; 3] = [None, Some(BinaryFormat::Hex), Some(BinaryFormat::Base64)];
+
+/// Column encodings inside a tile; each column gets whichever is smallest
+/// Every cell in row order
+const COL_PLAIN: u8 = 0;
+/// Runs: [varint run length][cell]
+const COL_RLE: u8 = 1;
+/// [varint entry count][cells], then a varint entry index per row
+const COL_DICT: u8 = 2;
+
+/// Column block flag: the block is deflated
+const COL_DEFLATE: u8 = 1;
+
+/// Spellings a boolean cell can round-trip through (false, true)
+const BOOL_SPELLINGS: [(&str, &str); 4] = [("0", "1"), ("false", "true"), ("FALSE", "TRUE"), ("False", "True")];
+
 //------- TileRowStore definition --------
 #[derive(Debug)]
 pub struct TileRowStore {
     /// Temp file (auto cleaned up)
     temp_file: Option<NamedTempFile>,
-    /// We need a persistent file handle for reading (can be reopened by path if needed)
-    file: std::io::BufReader<File>,
-    /// Offsets of each tile block
-    tile_offsets: Vec<u64>,
-    /// Row count for each tile (last tile may be short)
-    tile_row_counts: Vec<u32>,
+    /// Where the tiles are read from
+    path: PathBuf,
+    reader: TileReader,
+    /// Where each tile is (the last one may be short)
+    tiles: Vec<TileEntry>,
     /// Total cols, total rows
     pub ncols: usize,
     pub nrows: usize,
-    /// Tile LRU: tile index -> Arc<Vec<Vec<String>>>
-    cache: LruCache<usize, Arc<Vec<Vec<String>>>>,
+    /// Logical type of each column (also stored in the file header)
+    pub column_types: Vec<ColumnType>,
+    /// This store's tiles and columns in `CACHE` are keyed by it
+    id: u64,
+    /// Background decoding for read-ahead, started on first use
+    prefetcher: Option<Prefetcher>,
     /// Always hold first/last tile in memory
-    first_tile: Option<Arc<Vec<Vec<String>>>>,
-    last_tile: Option<Arc<Vec<Vec<String>>>>,
+    first_tile: Option<Arc<Tile>>,
+    last_tile: Option<Arc<Tile>>,
+    /// Set while a `TileWriter` is still appending to the file
+    live: Option<Arc<Mutex<LiveIndex>>>,
+    /// Full bytes of cells that only have a preview in the tiles
+    spill: Option<Arc<ValueSpill>>,
 }
 
 impl TileRowStore {
-    /// Write entire rowset from an iterator, with column count
-    /// Returns: (headers, store)
-
-    pub fn prefetch_for_view(&mut self, view_row: usize, max_rows: usize) {
-        let tile_count = self.tile_offsets.len();
-        if tile_count == 0 { return; }
+    /// Queue the tiles around the view for the prefetch thread: a few ahead
+    /// when the view is moving, one on each side when it isn't
+    pub fn prefetch_for_view(&mut self, view_row: usize, max_rows: usize, ahead: ReadAhead) {
+        self.take_prefetched(None);
+        if self.tiles.is_empty() || max_rows == 0 { return; }
         let start_tile = view_row / TILE_SIZE;
         let end_tile = (view_row + max_rows - 1) / TILE_SIZE;
-        // One neighbor below and above, bounded by tile_count.
-        let prefetch_start = start_tile.saturating_sub(1);
-        let prefetch_end = (end_tile+1).min(tile_count-1);
-        for t in prefetch_start..=prefetch_end {
-            let _ = self.load_tile_arc(t);
-        }
-    }
-
-    pub fn from_rows<I>(
-        headers: &[String],
-        rows_iter: I,
-    ) -> io::Result<Self>
-    where
-        I: Iterator<Item = Vec<String>>,
-    {
-        let mut temp_file = NamedTempFile::new()?;
-        let mut file = BufWriter::with_capacity(256 * 1024, temp_file.as_file_mut());
-        // Write header
-        file.write_all(MAGIC)?;
-        file.write_u32::<LittleEndian>(TILE_SIZE as u32)?;
-        file.write_u32::<LittleEndian>(headers.len() as u32)?;
-        // Placeholders:
-        let row_count_pos = file.stream_position()?;
-        file.write_u32::<LittleEndian>(0)?;
-        let tile_count_pos = file.stream_position()?;
-        file.write_u32::<LittleEndian>(0)?;
+        let wanted = match ahead {
+            ReadAhead::Forward => end_tile + 1..=end_tile + READ_AHEAD_TILES,
+            ReadAhead::Backward => start_tile.saturating_sub(READ_AHEAD_TILES)..=start_tile.saturating_sub(1),
+            ReadAhead::Both => start_tile.saturating_sub(1)..=end_tile + 1,
+        };
+        for idx in wanted {
+            self.request_tile(idx);
+        }
+    }
 
-        // Tiles:
-        let mut tile_offsets: Vec<u64> = Vec::new();
-        let mut tile_row_counts: Vec<u32> = Vec::new();
-        let mut buf_tile: Vec<Vec<String>> = Vec::with_capacity(TILE_SIZE);
+    fn request_tile(&mut self, idx: usize) {
+        // first/last are held outside the cache (and can still change while live)
+        if idx == 0 || idx + 1 >= self.tiles.len() || CACHE.lock().unwrap().tiles.contains(&(self.id, idx)) {
+            return;
+        }
+        if self.prefetcher.is_none() {
+            self.prefetcher = Prefetcher::spawn(&self.path, self.ncols).ok();
+        }
+        let Some(prefetcher) = &mut self.prefetcher else { return };
+        if prefetcher.pending.len() >= MAX_PENDING_PREFETCH || !prefetcher.pending.insert(idx) {
+            return;
+        }
+        if prefetcher.requests.send((idx, self.tiles[idx])).is_err() {
+            prefetcher.pending.remove(&idx);
+        }
+    }
 
-        let mut nrows = 0usize;
-        for row in rows_iter {
-            buf_tile.push(row);
-            nrows += 1;
-            if buf_tile.len() == TILE_SIZE {
-                tile_offsets.push(file.stream_position()?);
-                Self::write_tile(&mut file, &buf_tile)?;
-                tile_row_counts.push(buf_tile.len() as u32);
-                buf_tile.clear();
+    /// Move tiles the prefetch thread has decoded into the cache. With
+    /// `wait_for`, waits for that tile if it's on its way and returns it.
+    fn take_prefetched(&mut self, wait_for: Option<usize>) -> Option<Arc<Tile>> {
+        let prefetcher = self.prefetcher.as_mut()?;
+        let mut arrived = Vec::new();
+        let mut wanted = None;
+        loop {
+            let wait = wait_for.is_some_and(|idx| prefetcher.pending.contains(&idx));
+            let next = if wait { prefetcher.ready.recv().ok() } else { prefetcher.ready.try_recv().ok() };
+            let Some((idx, tile)) = next else { break };
+            prefetcher.pending.remove(&idx);
+            // failed tiles are read again (and the error reported) on demand
+            if let Ok(tile) = tile {
+                if Some(idx) == wait_for {
+                    wanted = Some(tile.clone());
+                }
+                arrived.push((idx, tile));
             }
         }
-
-        // Write last (possibly short) tile
-        if !buf_tile.is_empty() {
-            tile_offsets.push(file.stream_position()?);
-            Self::write_tile(&mut file, &buf_tile)?;
-            tile_row_counts.push(buf_tile.len() as u32);
-            buf_tile.clear();
+        for (idx, tile) in arrived {
+            self.cache_tile(idx, tile);
         }
+        wanted
+    }
+
+    fn cache_tile(&mut self, idx: usize, tile: Arc<Tile>) {
+        CACHE.lock().unwrap().put_tile((self.id, idx), tile);
+    }
+
+    /// Drop whatever is cached for tile `first` and the ones after it
+    fn forget_tiles_from(&mut self, first: usize) {
+        CACHE.lock().unwrap().forget(self.id, first);
+    }
+
+    /// Open a read handle on a tile file whose tiles are described by `index`.
+    fn open(path: &Path, column_types: Vec<ColumnType>, index: &LiveIndex) -> io::Result<Self> {
+        Ok(TileRowStore {
+            temp_file: None,
+            path: path.to_path_buf(),
+            reader: TileReader::open(path, column_types.len())?,
+            tiles: index.tiles.clone(),
+            ncols: column_types.len(),
+            nrows: index.nrows,
+            column_types,
+            id: NEXT_STORE_ID.fetch_add(1, Ordering::Relaxed),
+            prefetcher: None,
+            first_tile: None,
+            last_tile: None,
+            live: None,
+            spill: None,
+        })
+    }
+
+    /// Oversized values of the cells in this store
+    pub fn spill(&self) -> Option<Arc<ValueSpill>> {
+        self.spill.clone()
+    }
 
-        // After data, write the tile offset table:
-        let _tile_table_pos = file.stream_position()?;
-        for &offset in &tile_offsets {
-            file.write_u64::<LittleEndian>(offset)?;
+    /// Hand over the oversized values, e.g. to a re-sorted copy of the rows
+    pub fn set_spill(&mut self, spill: Option<Arc<ValueSpill>>) {
+        self.spill = spill;
+    }
+
+    /// The complete text of a cell from this store; oversized values are
+    /// read back from the spill file
+    pub fn full_value(&self, cell: &str) -> io::Result<String> {
+        match &self.spill {
+            Some(spill) => spill.full_value(cell),
+            None => Ok(cell_value::unescape(cell).to_string()),
         }
-        for &row_count in &tile_row_counts {
-            file.write_u32::<LittleEndian>(row_count)?;
+    }
+
+    /// Like [`get_rows`](Self::get_rows), with oversized values in full
+    pub fn get_full_rows(&mut self, start: usize, count: usize) -> io::Result<Vec<Vec<String>>> {
+        let mut rows = self.get_rows(start, count)?;
+        for cell in rows.iter_mut().flatten() {
+            if LargeValue::parse(cell).is_some() || cell_value::unescape(cell).len() < cell.len() {
+                *cell = self.full_value(cell)?;
+            }
         }
+        Ok(rows)
+    }
 
-        // Patch row count / tile count
-        file.seek(SeekFrom::Start(row_count_pos))?;
-        file.write_u32::<LittleEndian>(nrows as u32)?;
-        file.seek(SeekFrom::Start(tile_count_pos))?;
-        file.write_u32::<LittleEndian>(tile_offsets.len() as u32)?;
+    /// Copy the finished tile file to `out`; returns how many bytes were written
+    pub fn write_tiles_to(&self, out: &mut impl Write) -> io::Result<u64> {
+        let temp = self.temp_file.as_ref()
+            .filter(|_| self.live.is_none())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Rows are still being fetched"))?;
+        io::copy(&mut File::open(temp.path())?, out)
+    }
 
-        // Now re-open as read handle (flush+read)
-        drop(file);
-        let temp_file_read = OpenOptions::new()
-            .read(true)
-            .write(true)
-            .open(temp_file.path())?;
+    /// Open a finished tile file that makes up the first `len` bytes of
+    /// `path` (see [`write_tiles_to`](Self::write_tiles_to)). The header and
+    /// footer are checked here, each tile's checksum when it is loaded.
+    pub fn open_saved(path: &Path, len: u64) -> io::Result<Self> {
+        let mut file = std::io::BufReader::new(File::open(path)?);
+        let column_types = read_header(&mut file)?;
+        let header_len = file.stream_position()?;
+        let index = read_footer(&mut file, header_len, len)?;
 
-        let buf_reader = std::io::BufReader::with_capacity(256 * 1024, temp_file_read);
+        let mut store = Self::open(path, column_types, &index)?;
+        if !store.tiles.is_empty() {
+            store.first_tile = Some(store.load_tile_arc(0)?);
+            store.last_tile = Some(store.load_tile_arc(store.tiles.len() - 1)?);
+        }
+        Ok(store)
+    }
 
+    /// Whether rows are still being appended by a `TileWriter`
+    pub fn is_live(&self) -> bool {
+        self.live.is_some()
+    }
 
-        let mut store = TileRowStore {
-            temp_file: Some(temp_file),
-            file: buf_reader,
-            tile_offsets,
-            tile_row_counts,
-            ncols: headers.len(),
-            nrows,
-            cache: LruCache::new(std::num::NonZeroUsize::new(6).unwrap()), // cache size of 6
-            first_tile: None,
-            last_tile: None,
-        };
+    /// The tile file this store reads
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
 
-        // Preload first/last tiles
-        if !store.tile_offsets.is_empty() {
-            store.first_tile = store.load_tile_arc(0).ok();
-            store.last_tile = store.load_tile_arc(store.tile_offsets.len() - 1).ok();
+    /// Pick up tiles the writer has published since the last call.
+    /// Returns `true` if new rows became visible.
+    pub fn sync(&mut self) -> bool {
+        let Some(live) = &self.live else { return false };
+        let index = live.lock().unwrap();
+        if index.nrows == self.nrows {
+            return false;
         }
+        // A short tail tile published at a row cap is overwritten in place
+        // once more rows arrive, so take the whole index rather than appending
+        // and forget what was cached for the tiles that changed.
+        let first_changed = self.tiles.first() != index.tiles.first();
+        let unchanged = self.tiles.iter().zip(&index.tiles).take_while(|(a, b)| a == b).count();
+        self.tiles = index.tiles.clone();
+        self.nrows = index.nrows;
+        drop(index);
+        self.forget_tiles_from(unchanged);
 
-        Ok(store)
+        self.last_tile = None;
+        if first_changed {
+            self.first_tile = self.load_tile_arc(0).ok();
+        }
+        true
     }
 
-    /// Write a full tile (rows) in format:
-    /// [row count: u32][col count: u32] then, for row in rows, col in row: [u32(len)][bytes]
-    fn write_tile<W: Write>(file: &mut W, rows: &[Vec<String>]) -> io::Result<()> {
-        file.write_u32::<LittleEndian>(rows.len() as u32)?;
-        file.write_u32::<LittleEndian>(if rows.is_empty() { 0 } else { rows[0].len() as u32 })?;
-        for row in rows {
-            for col in row {
-                let bytes = col.as_bytes();
-                file.write_u32::<LittleEndian>(bytes.len() as u32)?;
-                file.write_all(bytes)?;
+    /// Encode a full tile (rows) in format:
+    /// [row count: u32][col count: u32][column directory] then each column's
+    /// block, deflated when that saves at least an eighth
+    fn encode_tile(types: &[ColumnType], rows: &[Vec<String>]) -> io::Result<Vec<u8>> {
+        if let Some(row) = rows.iter().find(|row| row.len() != types.len()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("row has {} cells, the result has {} columns", row.len(), types.len()),
+            ));
+        }
+        let mut head = Vec::with_capacity(tile_head_len(types.len()));
+        head.write_u32::<LittleEndian>(rows.len() as u32)?;
+        head.write_u32::<LittleEndian>(types.len() as u32)?;
+        let mut blocks = Vec::new();
+        let mut cells = Vec::with_capacity(rows.len());
+        let mut data = Vec::new();
+        for (col, ty) in types.iter().enumerate() {
+            cells.clear();
+            cells.extend(rows.iter().map(|row| row[col].as_str()));
+            data.clear();
+            Self::encode_column(&mut data, *ty, &cells)?;
+
+            let mut deflater = DeflateEncoder::new(Vec::new(), Compression::fast());
+            deflater.write_all(&data)?;
+            let deflated = deflater.finish()?;
+            let (flags, block) = if deflated.len() < data.len() - data.len() / 8 {
+                (COL_DEFLATE, deflated.as_slice())
+            } else {
+                (0, data.as_slice())
+            };
+            head.write_u32::<LittleEndian>(block.len() as u32)?;
+            head.write_u32::<LittleEndian>(data.len() as u32)?;
+            head.write_u8(flags)?;
+            head.write_u32::<LittleEndian>(crc32(block))?;
+            blocks.extend_from_slice(block);
+        }
+        head.extend_from_slice(&blocks);
+        Ok(head)
+    }
+
+    /// Append one column of a tile, plain, run-length or dictionary encoded,
+    /// whichever comes out smallest
+    fn encode_column(out: &mut Vec<u8>, ty: ColumnType, cells: &[&str]) -> io::Result<()> {
+        let mut best = vec![COL_PLAIN];
+        for cell in cells {
+            Self::write_cell(&mut best, ty, cell)?;
+        }
+
+        let mut runs = vec![COL_RLE];
+        for run in cells.chunk_by(|a, b| a == b) {
+            write_varint(&mut runs, run.len() as u64)?;
+            Self::write_cell(&mut runs, ty, run[0])?;
+            if runs.len() >= best.len() {
+                break;
             }
         }
+        if runs.len() < best.len() {
+            best = runs;
+        }
+
+        // only worth trying while at least every other value repeats
+        let mut ids: HashMap<&str, u64> = HashMap::new();
+        let mut entries = Vec::new();
+        let mut indices = Vec::with_capacity(cells.len());
+        for cell in cells {
+            let next = ids.len() as u64;
+            let id = *ids.entry(cell).or_insert_with(|| {
+                entries.push(*cell);
+                next
+            });
+            if ids.len() > cells.len() / 2 {
+                break;
+            }
+            indices.push(id);
+        }
+        if indices.len() == cells.len() {
+            let mut dict = vec![COL_DICT];
+            write_varint(&mut dict, entries.len() as u64)?;
+            for entry in &entries {
+                Self::write_cell(&mut dict, ty, entry)?;
+            }
+            for id in indices {
+                write_varint(&mut dict, id)?;
+            }
+            if dict.len() < best.len() {
+                best = dict;
+            }
+        }
+
+        out.extend_from_slice(&best);
         Ok(())
     }
 
-    /// Loads an Arc'd tile from file (by tile index)
-    fn load_tile_arc(&mut self, idx: usize) -> io::Result<Arc<Vec<Vec<String>>>> {
-        let offset = *self.tile_offsets.get(idx)
-            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "tile idx out of range"))?;
-        self.file.seek(SeekFrom::Start(offset))?;
+    /// Read back one column written by [`encode_column`](Self::encode_column)
+    fn decode_column(r: &mut &[u8], rows: usize) -> io::Result<Vec<String>> {
+        let mut cells = Vec::with_capacity(rows);
+        match r.read_u8()? {
+            COL_PLAIN => {
+                for _ in 0..rows {
+                    cells.push(Self::read_cell(r)?);
+                }
+            }
+            COL_RLE => {
+                while cells.len() < rows {
+                    let run = read_varint(r)?;
+                    if run == 0 || run > (rows - cells.len()) as u64 {
+                        return Err(corrupt("bad run length"));
+                    }
+                    let cell = Self::read_cell(r)?;
+                    cells.resize(cells.len() + run as usize, cell);
+                }
+            }
+            COL_DICT => {
+                let count = read_varint(r)?;
+                if count > rows as u64 {
+                    return Err(corrupt("dictionary is larger than its column"));
+                }
+                let entries = (0..count).map(|_| Self::read_cell(r)).collect::<io::Result<Vec<_>>>()?;
+                for _ in 0..rows {
+                    let id = read_varint(r)?;
+                    let cell = entries.get(id as usize).ok_or_else(|| corrupt("dictionary index out of range"))?;
+                    cells.push(cell.clone());
+                }
+            }
+            encoding => return Err(corrupt(&format!("unknown column encoding {}", encoding))),
+        }
+        Ok(cells)
+    }
+
+    /// Encode one cell, using the column type when the value round-trips exactly
+    fn write_cell<W: Write>(file: &mut W, ty: ColumnType, cell: &str) -> io::Result<()> {
+        if cell == NULL_SENTINEL {
+            return file.write_u8(CELL_NULL);
+        }
+        if let Some(large) = LargeValue::parse(cell) {
+            let kind = LARGE_KINDS.iter().position(|k| *k == large.binary).unwrap_or(0);
+            file.write_u8(CELL_LARGE)?;
+            file.write_u64::<LittleEndian>(large.offset)?;
+            file.write_u64::<LittleEndian>(large.bytes)?;
+            file.write_u8(kind as u8)?;
+            write_varint(file, large.preview.len() as u64)?;
+            return file.write_all(large.preview.as_bytes());
+        }
+        match ty {
+            ColumnType::Integer => {
+                if let Ok(i) = cell.parse::<i64>() {
+                    if i.to_string() == cell {
+                        file.write_u8(CELL_INT)?;
+                        return file.write_i64::<LittleEndian>(i);
+                    }
+                }
+            }
+            ColumnType::Float => {
+                if let Ok(f) = cell.parse::<f64>() {
+                    if f.to_string() == cell {
+                        file.write_u8(CELL_FLOAT)?;
+                        return file.write_f64::<LittleEndian>(f);
+                    }
+                }
+            }
+            ColumnType::Boolean => {
+                if let Some(b) = parse_bool(cell) {
+                    if let Some(spelling) = BOOL_SPELLINGS.iter()
+                        .position(|(f, t)| cell == if b { *t } else { *f })
+                    {
+                        file.write_u8(CELL_BOOL)?;
+                        return file.write_u8((spelling as u8) << 1 | b as u8);
+                    }
+                }
+            }
+            ColumnType::Date => {
+                if let Ok(d) = NaiveDate::parse_from_str(cell, DATE_FORMAT) {
+                    if d.format(DATE_FORMAT).to_string() == cell {
+                        file.write_u8(CELL_DATE)?;
+                        return file.write_i32::<LittleEndian>(d.num_days_from_ce());
+                    }
+                }
+            }
+            ColumnType::Timestamp => {
+                if let Some(ts) = parse_timestamp(cell) {
+                    let digits = cell.rfind('.').map(|dot| cell.len() - dot - 1).unwrap_or(0);
+                    let nanos = ts.nanosecond();
+                    if digits <= 9 && Self::format_timestamp(ts.and_utc().timestamp(), nanos, digits as u8).as_deref() == Some(cell) {
+                        file.write_u8(CELL_TIMESTAMP)?;
+                        file.write_i64::<LittleEndian>(ts.and_utc().timestamp())?;
+                        file.write_u32::<LittleEndian>(nanos)?;
+                        return file.write_u8(digits as u8);
+                    }
+                }
+            }
+            ColumnType::Text | ColumnType::Decimal | ColumnType::Time | ColumnType::Binary => {}
+        }
+        let bytes = cell.as_bytes();
+        file.write_u8(CELL_TEXT)?;
+        write_varint(file, bytes.len() as u64)?;
+        file.write_all(bytes)
+    }
+
+    fn format_timestamp(secs: i64, nanos: u32, digits: u8) -> Option<String> {
+        let ts = chrono::DateTime::from_timestamp(secs, nanos)?.naive_utc();
+        let mut out = ts.format(TIMESTAMP_FORMAT).to_string();
+        if digits > 0 {
+            let frac = format!("{:09}", nanos);
+            out.push('.');
+            out.push_str(&frac[..digits as usize]);
+        }
+        Some(out)
+    }
+
+    fn read_cell(r: &mut &[u8]) -> io::Result<String> {
+        Ok(match r.read_u8()? {
+            CELL_NULL => NULL_SENTINEL.to_string(),
+            CELL_TEXT => Self::read_text(r)?,
+            CELL_INT => r.read_i64::<LittleEndian>()?.to_string(),
+            CELL_FLOAT => r.read_f64::<LittleEndian>()?.to_string(),
+            CELL_BOOL => {
+                let code = r.read_u8()?;
+                let (f, t) = BOOL_SPELLINGS.get((code >> 1) as usize).ok_or_else(|| corrupt("bad boolean cell"))?;
+                if code & 1 == 1 { t.to_string() } else { f.to_string() }
+            }
+            CELL_DATE => {
+                let days = r.read_i32::<LittleEndian>()?;
+                NaiveDate::from_num_days_from_ce_opt(days)
+                    .ok_or_else(|| corrupt("bad date cell"))?
+                    .format(DATE_FORMAT)
+                    .to_string()
+            }
+            CELL_TIMESTAMP => {
+                let secs = r.read_i64::<LittleEndian>()?;
+                let nanos = r.read_u32::<LittleEndian>()?;
+                let digits = r.read_u8()?;
+                if digits > 9 {
+                    return Err(corrupt("timestamp cell has more than 9 fractional digits"));
+                }
+                Self::format_timestamp(secs, nanos, digits).ok_or_else(|| corrupt("bad timestamp cell"))?
+            }
+            CELL_LARGE => {
+                let offset = r.read_u64::<LittleEndian>()?;
+                let bytes = r.read_u64::<LittleEndian>()?;
+                let binary = *LARGE_KINDS.get(r.read_u8()? as usize).ok_or_else(|| corrupt("bad spilled value cell"))?;
+                let preview = Self::read_text(r)?;
+                LargeValue { offset, bytes, binary, preview: &preview }.marker()
+            }
+            tag => return Err(corrupt(&format!("unknown cell tag {}", tag))),
+        })
+    }
+
+    /// A varint length and that many bytes of text
+    fn read_text(r: &mut &[u8]) -> io::Result<String> {
+        let len = read_varint(r)?;
+        if len > r.len() as u64 {
+            return Err(corrupt("text cell runs past the end of its tile"));
+        }
+        let (bytes, rest) = r.split_at(len as usize);
+        *r = rest;
+        Ok(String::from_utf8_lossy(bytes).to_string())
+    }
+
+    /// Check and decode one column block of tile `idx`
+    fn decode_block(idx: usize, col: usize, block: &ColumnBlock, bytes: &[u8], rows: usize) -> io::Result<Vec<String>> {
+        if crc32(bytes) != block.checksum {
+            return Err(corrupt(&format!("tile {} column {} failed its checksum", idx, col)));
+        }
+        // deflate can't shrink anything by more than about 1032:1
+        let most = if block.flags & COL_DEFLATE != 0 { bytes.len().saturating_mul(1032) } else { bytes.len() };
+        if block.data_len as usize > most {
+            return Err(corrupt(&format!("tile {} column {} claims more data than it can hold", idx, col)));
+        }
+        let inflated;
+        let mut data = if block.flags & COL_DEFLATE != 0 {
+            let mut out = Vec::with_capacity(block.data_len as usize);
+            DeflateDecoder::new(bytes).take(block.data_len as u64 + 1).read_to_end(&mut out)
+                .map_err(|_| corrupt(&format!("tile {} column {} doesn't decompress", idx, col)))?;
+            inflated = out;
+            inflated.as_slice()
+        } else {
+            bytes
+        };
+        if data.len() != block.data_len as usize {
+            return Err(corrupt(&format!("tile {} column {} has the wrong length", idx, col)));
+        }
+        let cells = Self::decode_column(&mut data, rows).map_err(|e| match e.kind() {
+            io::ErrorKind::UnexpectedEof => corrupt(&format!("tile {} column {} ends too early", idx, col)),
+            _ => e,
+        })?;
+        if !data.is_empty() {
+            return Err(corrupt(&format!("tile {} column {} has trailing bytes", idx, col)));
+        }
+        Ok(cells)
+    }
+
+    fn entry(&self, idx: usize) -> io::Result<TileEntry> {
+        self.tiles.get(idx).copied()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "tile idx out of range"))
+    }
+
+    /// Loads an Arc'd tile from file (by tile index), checking it against
+    /// its index entry
+    fn load_tile_arc(&mut self, idx: usize) -> io::Result<Arc<Tile>> {
+        let entry = self.entry(idx)?;
+        self.reader.load_tile(idx, &entry)
+    }
 
-        let row_count = self.file.read_u32::<LittleEndian>()? as usize;
-        let col_count = self.file.read_u32::<LittleEndian>()? as usize;
-        let mut rows = Vec::with_capacity(row_count);
-        for _ in 0..row_count {
-            let mut row = Vec::with_capacity(col_count);
-            for _ in 0..col_count {
-                let len = self.file.read_u32::<LittleEndian>()? as usize;
-                let mut buf = vec![0u8; len];
-                self.file.read_exact(&mut buf)?;
-                row.push(String::from_utf8_lossy(&buf).to_string());
+    /// Read a single column of tile `idx`, leaving the others on disk
+    fn load_column(&mut self, idx: usize, col: usize) -> io::Result<Vec<String>> {
+        let entry = self.entry(idx)?;
+        self.reader.load_column(idx, &entry, col)
+    }
+
+    /// Tile `idx` from memory, loading (and caching) it if needed
+    fn tile(&mut self, idx: usize) -> io::Result<Arc<Tile>> {
+        if idx == 0 || idx + 1 == self.tiles.len() {
+            let held = if idx == 0 { &self.first_tile } else { &self.last_tile };
+            if let Some(t) = held {
+                return Ok(t.clone());
             }
-            rows.push(row);
+            let t = self.load_tile_arc(idx)?;
+            if idx == 0 {
+                self.first_tile = Some(t.clone());
+            } else {
+                self.last_tile = Some(t.clone());
+            }
+            return Ok(t);
+        }
+        if let Some(t) = CACHE.lock().unwrap().tiles.get(&(self.id, idx)) {
+            return Ok(t.clone());
+        }
+        if let Some(t) = self.take_prefetched(Some(idx)) {
+            return Ok(t);
         }
-        Ok(Arc::new(rows))
+        let t = self.load_tile_arc(idx)?;
+        self.cache_tile(idx, t.clone());
+        Ok(t)
     }
 
     /// Fetches rows from start..(start+count).
@@ -193,29 +656,604 @@ impl TileRowStore {
         while curr < end {
             let tile_idx = curr / TILE_SIZE;
             let in_tile = curr % TILE_SIZE;
-            let tile = if tile_idx == 0 {
-                self.first_tile.as_ref().cloned()
-                    .or_else(|| self.load_tile_arc(0).ok())
-            } else if tile_idx == self.tile_offsets.len()-1 {
-                self.last_tile.as_ref().cloned()
-                    .or_else(|| self.load_tile_arc(tile_idx).ok())
-            } else {
-                if let Some(t) = self.cache.get(&tile_idx) {
-                    Some(t.clone())
-                } else {
-                    let t = self.load_tile_arc(tile_idx)?;
-                    self.cache.put(tile_idx, t.clone());
-                    Some(t)
-                }
-            }.ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Failed to load tile"))?;
-            let end_in_tile = usize::min(tile.len(), in_tile + (end-curr));
-            for row in &tile[in_tile..end_in_tile] {
-                result.push(row.clone());
+            let tile = self.tile(tile_idx)?;
+            let end_in_tile = usize::min(tile.rows, in_tile + (end-curr));
+            if end_in_tile <= in_tile {
+                return Err(corrupt(&format!("tile {} is shorter than the file index says", tile_idx)));
+            }
+            for row in in_tile..end_in_tile {
+                result.push(tile.columns.iter().map(|column| column[row].clone()).collect());
+            }
+            curr += end_in_tile - in_tile;
+        }
+        Ok(result)
+    }
+
+    /// The cells of column `col` (0-based) for `rows`. Only that column is
+    /// read and decoded for tiles that aren't already in memory, which makes
+    /// scans over one column of a wide result cheap.
+    pub fn get_column(&mut self, col: usize, rows: Range<usize>) -> io::Result<Vec<String>> {
+        if col >= self.ncols {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "column out of range"));
+        }
+        let end = rows.end.min(self.nrows);
+        let mut result = Vec::with_capacity(end.saturating_sub(rows.start));
+        let mut curr = rows.start;
+        while curr < end {
+            let tile_idx = curr / TILE_SIZE;
+            let in_tile = curr % TILE_SIZE;
+            let column = self.tile_column(tile_idx, col)?;
+            let end_in_tile = usize::min(column.len(), in_tile + (end-curr));
+            if end_in_tile <= in_tile {
+                return Err(corrupt(&format!("tile {} is shorter than the file index says", tile_idx)));
             }
+            result.extend_from_slice(&column[in_tile..end_in_tile]);
             curr += end_in_tile - in_tile;
         }
         Ok(result)
     }
+
+    fn tile_column(&mut self, idx: usize, col: usize) -> io::Result<Arc<Vec<String>>> {
+        // the first and last tiles can still change while live, so they're
+        // only kept whole
+        if idx == 0 || idx + 1 == self.tiles.len() {
+            return Ok(self.tile(idx)?.columns[col].clone());
+        }
+        {
+            let mut cache = CACHE.lock().unwrap();
+            if let Some(t) = cache.tiles.peek(&(self.id, idx)) {
+                return Ok(t.columns[col].clone());
+            }
+            if let Some(c) = cache.columns.get(&(self.id, idx, col)) {
+                return Ok(c.clone());
+            }
+        }
+        let column = Arc::new(self.load_column(idx, col)?);
+        CACHE.lock().unwrap().put_column((self.id, idx, col), column.clone());
+        Ok(column)
+    }
+}
+
+/// Set the memory budget shared by all stores' decoded tiles
+/// (`tile_cache_mb` in Frost.toml)
+pub fn set_cache_budget(bytes: usize) {
+    let mut cache = CACHE.lock().unwrap();
+    cache.budget = bytes;
+    cache.trim();
+}
+
+/// Tiles, and single columns read by `get_column`, keyed by store id and
+/// tile index (and column)
+struct TileCache {
+    tiles: LruCache<(u64, usize), Arc<Tile>>,
+    columns: LruCache<(u64, usize, usize), Arc<Vec<String>>>,
+    /// Estimated bytes held by both, and the most they may hold
+    bytes: usize,
+    budget: usize,
+}
+
+impl TileCache {
+    fn new(budget: usize) -> Self {
+        Self { tiles: LruCache::unbounded(), columns: LruCache::unbounded(), bytes: 0, budget }
+    }
+
+    fn put_tile(&mut self, key: (u64, usize), tile: Arc<Tile>) {
+        self.bytes += tile.bytes;
+        if let Some(old) = self.tiles.put(key, tile) {
+            self.bytes -= old.bytes;
+        }
+        self.trim();
+    }
+
+    fn put_column(&mut self, key: (u64, usize, usize), column: Arc<Vec<String>>) {
+        self.bytes += column_bytes(&column);
+        if let Some(old) = self.columns.put(key, column) {
+            self.bytes -= column_bytes(&old);
+        }
+        self.trim();
+    }
+
+    /// Drop the entries of `store` from tile `first` on
+    fn forget(&mut self, store: u64, first: usize) {
+        let stale: Vec<_> = self.tiles.iter().map(|(&key, _)| key).filter(|&(s, idx)| s == store && idx >= first).collect();
+        for key in stale {
+            if let Some(tile) = self.tiles.pop(&key) {
+                self.bytes -= tile.bytes;
+            }
+        }
+        let stale: Vec<_> =
+            self.columns.iter().map(|(&key, _)| key).filter(|&(s, idx, _)| s == store && idx >= first).collect();
+        for key in stale {
+            if let Some(column) = self.columns.pop(&key) {
+                self.bytes -= column_bytes(&column);
+            }
+        }
+    }
+
+    /// Drop least recently used columns, then tiles, until both fit the budget
+    fn trim(&mut self) {
+        while self.bytes > self.budget {
+            let freed = if let Some((_, column)) = self.columns.pop_lru() {
+                column_bytes(&column)
+            } else if let Some((_, tile)) = self.tiles.pop_lru() {
+                tile.bytes
+            } else {
+                break;
+            };
+            self.bytes -= freed;
+        }
+    }
+}
+
+/// Which tiles `prefetch_for_view` reads ahead
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReadAhead {
+    /// Scrolling down
+    Forward,
+    /// Scrolling up
+    Backward,
+    /// Not scrolling: one tile either side
+    Both,
+}
+
+/// A decoded tile, column by column
+#[derive(Debug)]
+struct Tile {
+    rows: usize,
+    columns: Vec<Arc<Vec<String>>>,
+    /// Rough memory use, for the cache budget
+    bytes: usize,
+}
+
+fn column_bytes(column: &[String]) -> usize {
+    column.iter().map(|cell| std::mem::size_of::<String>() + cell.capacity()).sum()
+}
+
+/// Reads and checks tiles through its own file handle
+#[derive(Debug)]
+struct TileReader {
+    file: std::io::BufReader<File>,
+    ncols: usize,
+}
+
+impl TileReader {
+    fn open(path: &Path, ncols: usize) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .open(path)?;
+        Ok(Self { file: std::io::BufReader::with_capacity(256 * 1024, file), ncols })
+    }
+
+    /// Read `len` bytes at `offset`
+    fn read_at(&mut self, idx: usize, offset: u64, len: usize) -> io::Result<Vec<u8>> {
+        self.file.seek(SeekFrom::Start(offset))?;
+        let mut buf = vec![0u8; len];
+        self.file.read_exact(&mut buf).map_err(|e| match e.kind() {
+            io::ErrorKind::UnexpectedEof => corrupt(&format!("tile {} is truncated", idx)),
+            _ => e,
+        })?;
+        Ok(buf)
+    }
+
+    /// Check a tile's row count and column directory (the part its index
+    /// checksum covers) and return where each column block is
+    fn parse_tile_head(&self, idx: usize, entry: &TileEntry, head: &[u8]) -> io::Result<Vec<ColumnBlock>> {
+        if crc32(head) != entry.checksum {
+            return Err(corrupt(&format!("tile {} failed its checksum", idx)));
+        }
+        let mut r = head;
+        let row_count = r.read_u32::<LittleEndian>()?;
+        let col_count = r.read_u32::<LittleEndian>()? as usize;
+        if row_count != entry.rows || col_count != self.ncols {
+            return Err(corrupt(&format!("tile {} doesn't match the file index", idx)));
+        }
+        let mut offset = head.len() as u64;
+        let mut blocks = Vec::with_capacity(col_count);
+        for _ in 0..col_count {
+            let block = ColumnBlock {
+                offset,
+                len: r.read_u32::<LittleEndian>()?,
+                data_len: r.read_u32::<LittleEndian>()?,
+                flags: r.read_u8()?,
+                checksum: r.read_u32::<LittleEndian>()?,
+            };
+            if block.flags & !COL_DEFLATE != 0 {
+                return Err(corrupt(&format!("tile {} has unknown column flags", idx)));
+            }
+            offset += block.len as u64;
+            blocks.push(block);
+        }
+        if offset != entry.len {
+            return Err(corrupt(&format!("tile {} column sizes don't add up", idx)));
+        }
+        Ok(blocks)
+    }
+
+    fn load_tile(&mut self, idx: usize, entry: &TileEntry) -> io::Result<Arc<Tile>> {
+        let buf = self.read_at(idx, entry.offset, entry.len as usize)?;
+        let head_len = tile_head_len(self.ncols).min(buf.len());
+        let blocks = self.parse_tile_head(idx, entry, &buf[..head_len])?;
+        let rows = entry.rows as usize;
+        let columns = blocks.iter().enumerate()
+            .map(|(col, block)| {
+                let bytes = &buf[block.offset as usize..][..block.len as usize];
+                TileRowStore::decode_block(idx, col, block, bytes, rows).map(Arc::new)
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+        let bytes = columns.iter().map(|column| column_bytes(column)).sum();
+        Ok(Arc::new(Tile { rows, columns, bytes }))
+    }
+
+    fn load_column(&mut self, idx: usize, entry: &TileEntry, col: usize) -> io::Result<Vec<String>> {
+        let head_len = tile_head_len(self.ncols).min(entry.len as usize);
+        let head = self.read_at(idx, entry.offset, head_len)?;
+        let blocks = self.parse_tile_head(idx, entry, &head)?;
+        let block = blocks[col];
+        let bytes = self.read_at(idx, entry.offset + block.offset, block.len as usize)?;
+        TileRowStore::decode_block(idx, col, &block, &bytes, entry.rows as usize)
+    }
+}
+
+/// Decodes tiles on a background thread so scrolling doesn't wait on disk
+/// and decompression. The thread exits when the store (and with it the
+/// request channel) is dropped.
+#[derive(Debug)]
+struct Prefetcher {
+    requests: Sender<(usize, TileEntry)>,
+    ready: Receiver<(usize, io::Result<Arc<Tile>>)>,
+    /// Requested and not yet taken from `ready`
+    pending: HashSet<usize>,
+}
+
+impl Prefetcher {
+    fn spawn(path: &Path, ncols: usize) -> io::Result<Self> {
+        let mut reader = TileReader::open(path, ncols)?;
+        let (requests, request_rx) = mpsc::channel::<(usize, TileEntry)>();
+        let (ready_tx, ready) = mpsc::channel();
+        thread::Builder::new()
+            .name("tile-prefetch".into())
+            .spawn(move || {
+                for (idx, entry) in request_rx {
+                    if ready_tx.send((idx, reader.load_tile(idx, &entry))).is_err() {
+                        break;
+                    }
+                }
+            })?;
+        Ok(Self { requests, ready, pending: HashSet::new() })
+    }
+}
+
+/// Where a column's block is inside its tile, and how to check it
+#[derive(Debug, Clone, Copy)]
+struct ColumnBlock {
+    offset: u64,
+    len: u32,
+    data_len: u32,
+    flags: u8,
+    checksum: u32,
+}
+
+fn tile_head_len(ncols: usize) -> usize {
+    8 + ncols * COLUMN_DIR_LEN
+}
+
+//------- TileWriter (appendable store) --------
+
+/// Where a tile is in the file and how to check it
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TileEntry {
+    offset: u64,
+    len: u64,
+    rows: u32,
+    checksum: u32,
+}
+
+/// Tiles that are fully on disk, shared between a writer and its live views
+#[derive(Debug, Default, Clone)]
+struct LiveIndex {
+    tiles: Vec<TileEntry>,
+    nrows: usize,
+}
+
+fn corrupt(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("Corrupt tile file: {}", msg))
+}
+
+/// LEB128, for cell lengths
+fn write_varint<W: Write>(w: &mut W, mut value: u64) -> io::Result<()> {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            return w.write_u8(byte);
+        }
+        w.write_u8(byte | 0x80)?;
+    }
+}
+
+fn read_varint(r: &mut &[u8]) -> io::Result<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = r.read_u8()?;
+        value |= ((byte & 0x7F) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(corrupt("length prefix is too long"))
+}
+
+/// Check the header written by [`TileWriter::new`]; returns the column types
+fn read_header<R: Read>(r: &mut R) -> io::Result<Vec<ColumnType>> {
+    let mut magic = [0u8; 4];
+    r.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "Not a Frost tile file"));
+    }
+    let version = r.read_u16::<LittleEndian>()?;
+    if version != FORMAT_VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Tile file version {} is not supported (this Frost reads version {})", version, FORMAT_VERSION),
+        ));
+    }
+    let _flags = r.read_u16::<LittleEndian>()?;
+    let tile_size = r.read_u32::<LittleEndian>()?;
+    if tile_size as usize != TILE_SIZE {
+        return Err(corrupt(&format!("tile size {} instead of {}", tile_size, TILE_SIZE)));
+    }
+    let ncols = r.read_u32::<LittleEndian>()?;
+    (0..ncols)
+        .map(|_| {
+            let tag = r.read_u8()?;
+            ColumnType::from_tag(tag).ok_or_else(|| corrupt(&format!("unknown column type {}", tag)))
+        })
+        .collect()
+}
+
+/// Read and check the footer of a tile file that ends at `len`; tiles must
+/// lie between the header and the footer
+fn read_footer<R: Read + Seek>(r: &mut R, header_len: u64, len: u64) -> io::Result<LiveIndex> {
+    let truncated = || corrupt("the file is truncated or was never finished");
+    let tail_pos = len.checked_sub(FOOTER_TAIL_LEN).filter(|&p| p >= header_len).ok_or_else(truncated)?;
+    r.seek(SeekFrom::Start(tail_pos))?;
+    let mut tail = [0u8; FOOTER_TAIL_LEN as usize];
+    r.read_exact(&mut tail).map_err(|_| truncated())?;
+    if &tail[28..] != FOOTER_MAGIC {
+        return Err(truncated());
+    }
+    let t = &mut &tail[..];
+    let nrows = t.read_u64::<LittleEndian>()?;
+    let ntiles = t.read_u64::<LittleEndian>()?;
+    let footer_pos = t.read_u64::<LittleEndian>()?;
+    let checksum = t.read_u32::<LittleEndian>()?;
+    let index_len = tail_pos.checked_sub(footer_pos).filter(|_| footer_pos >= header_len);
+    if index_len.is_none() || ntiles.checked_mul(INDEX_ENTRY_LEN) != index_len {
+        return Err(corrupt("the tile index is the wrong size"));
+    }
+
+    r.seek(SeekFrom::Start(footer_pos))?;
+    let mut footer = vec![0u8; (tail_pos - footer_pos) as usize];
+    r.read_exact(&mut footer)?;
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(&footer);
+    hasher.update(&tail[..24]);
+    if hasher.finalize() != checksum {
+        return Err(corrupt("the tile index failed its checksum"));
+    }
+
+    let f = &mut footer.as_slice();
+    let mut index = LiveIndex { tiles: Vec::with_capacity(ntiles as usize), nrows: 0 };
+    for i in 0..ntiles {
+        let entry = TileEntry {
+            offset: f.read_u64::<LittleEndian>()?,
+            len: f.read_u64::<LittleEndian>()?,
+            rows: f.read_u32::<LittleEndian>()?,
+            checksum: f.read_u32::<LittleEndian>()?,
+        };
+        let in_bounds = entry.offset >= header_len
+            && entry.offset.checked_add(entry.len).is_some_and(|end| end <= footer_pos);
+        // get_rows finds rows by tile number, so only the last tile may be short
+        let full = entry.rows as usize == TILE_SIZE || (i + 1 == ntiles && entry.rows as usize <= TILE_SIZE);
+        if !in_bounds || !full {
+            return Err(corrupt(&format!("index entry {} is invalid", i)));
+        }
+        index.nrows += entry.rows as usize;
+        index.tiles.push(entry);
+    }
+    if index.nrows as u64 != nrows {
+        return Err(corrupt("tile row counts don't add up to the row count"));
+    }
+    Ok(index)
+}
+
+/// Appends rows to a tile file one at a time. Live views handed out by
+/// [`TileWriter::live_view`] can read every completed tile while the writer
+/// is still going; [`TileWriter::finish`] seals the file and returns the
+/// final store.
+pub struct TileWriter {
+    temp_file: NamedTempFile,
+    file: BufWriter<File>,
+    column_types: Vec<ColumnType>,
+    buf_tile: Vec<Vec<String>>,
+    /// Rows of `buf_tile` already visible as a short tail tile (see `publish_partial`)
+    published_partial: usize,
+    /// Where that short tile starts; it is always the last thing in the file
+    partial_offset: Option<u64>,
+    rows_written: usize,
+    index: Arc<Mutex<LiveIndex>>,
+    spill: Option<Arc<ValueSpill>>,
+}
+
+impl TileWriter {
+    pub fn new(headers: &[String], column_types: &[ColumnType]) -> io::Result<Self> {
+        let column_types: Vec<ColumnType> = (0..headers.len())
+            .map(|i| column_types.get(i).copied().unwrap_or(ColumnType::Text))
+            .collect();
+
+        let temp_file = NamedTempFile::new()?;
+        let mut file = BufWriter::with_capacity(256 * 1024, temp_file.as_file().try_clone()?);
+        // Write header; counts go in the footer
+        file.write_all(MAGIC)?;
+        file.write_u16::<LittleEndian>(FORMAT_VERSION)?;
+        file.write_u16::<LittleEndian>(0)?;
+        file.write_u32::<LittleEndian>(TILE_SIZE as u32)?;
+        file.write_u32::<LittleEndian>(headers.len() as u32)?;
+        // Column types, one tag byte each
+        for ty in &column_types {
+            file.write_u8(ty.tag())?;
+        }
+
+        Ok(Self {
+            temp_file,
+            file,
+            column_types,
+            buf_tile: Vec::with_capacity(TILE_SIZE),
+            published_partial: 0,
+            partial_offset: None,
+            rows_written: 0,
+            index: Arc::new(Mutex::new(LiveIndex::default())),
+            spill: None,
+        })
+    }
+
+    /// Where the row source put oversized values; passed on to the stores
+    pub fn set_spill(&mut self, spill: Option<Arc<ValueSpill>>) {
+        self.spill = spill;
+    }
+
+    /// The tile file being written; live views read the same one
+    pub fn path(&self) -> &Path {
+        self.temp_file.path()
+    }
+
+    /// Rows pushed so far (including ones not yet flushed to a tile)
+    pub fn rows_written(&self) -> usize {
+        self.rows_written
+    }
+
+    /// Append a row. Returns `true` when it completed a tile, i.e. live
+    /// views have new rows to show.
+    pub fn push_row(&mut self, row: Vec<String>) -> io::Result<bool> {
+        self.buf_tile.push(row);
+        self.rows_written += 1;
+        if self.buf_tile.len() < TILE_SIZE {
+            return Ok(false);
+        }
+        self.flush_tile()?;
+        Ok(true)
+    }
+
+    /// A read-only view that follows the writer via [`TileRowStore::sync`]
+    pub fn live_view(&self) -> io::Result<TileRowStore> {
+        let mut store = TileRowStore::open(
+            self.temp_file.path(),
+            self.column_types.clone(),
+            &self.index.lock().unwrap(),
+        )?;
+        store.live = Some(Arc::clone(&self.index));
+        store.spill = self.spill.clone();
+        if !store.tiles.is_empty() {
+            store.first_tile = store.load_tile_arc(0).ok();
+        }
+        Ok(store)
+    }
+
+    /// Make the rows of an incomplete tile visible to live views, e.g. when
+    /// fetching pauses at a row cap. The short tile is overwritten in place
+    /// (and its index entry replaced) when it grows, fills up or the writer
+    /// finishes.
+    pub fn publish_partial(&mut self) -> io::Result<()> {
+        if self.buf_tile.len() == self.published_partial {
+            return Ok(());
+        }
+        self.partial_offset = Some(self.write_buffered_tile()?);
+        self.published_partial = self.buf_tile.len();
+        Ok(())
+    }
+
+    fn flush_tile(&mut self) -> io::Result<()> {
+        if self.buf_tile.len() != self.published_partial {
+            self.write_buffered_tile()?;
+        }
+        self.buf_tile.clear();
+        self.published_partial = 0;
+        self.partial_offset = None;
+        Ok(())
+    }
+
+    /// Write `buf_tile` at the end of the file, or over the short tile it
+    /// replaces, and add it to the index. Returns where it starts.
+    fn write_buffered_tile(&mut self) -> io::Result<u64> {
+        let tile = TileRowStore::encode_tile(&self.column_types, &self.buf_tile)?;
+        if let Some(offset) = self.partial_offset {
+            // Withdraw the short tile before its bytes change under readers
+            let mut index = self.index.lock().unwrap();
+            index.tiles.pop();
+            index.nrows -= self.published_partial;
+            drop(index);
+            self.file.seek(SeekFrom::Start(offset))?;
+        }
+        let offset = self.file.stream_position()?;
+        self.file.write_all(&tile)?;
+        // Live views read the file as soon as the entry shows up in the index
+        self.file.flush()?;
+        let mut index = self.index.lock().unwrap();
+        index.tiles.push(TileEntry {
+            offset,
+            len: tile.len() as u64,
+            rows: self.buf_tile.len() as u32,
+            // column blocks carry their own checksums
+            checksum: crc32(&tile[..tile_head_len(self.column_types.len())]),
+        });
+        index.nrows += self.buf_tile.len();
+        Ok(offset)
+    }
+
+    /// Write the last (possibly short) tile and the footer, and return
+    /// the finished store. Live views keep working but stop growing.
+    pub fn finish(mut self) -> io::Result<TileRowStore> {
+        if !self.buf_tile.is_empty() {
+            self.flush_tile()?;
+        }
+
+        let index = self.index.lock().unwrap().clone();
+        let file = &mut self.file;
+
+        // Footer: the tile index, then the counts and the index position
+        let footer_pos = file.stream_position()?;
+        let mut footer = Vec::with_capacity(index.tiles.len() * INDEX_ENTRY_LEN as usize + 24);
+        for tile in &index.tiles {
+            footer.write_u64::<LittleEndian>(tile.offset)?;
+            footer.write_u64::<LittleEndian>(tile.len)?;
+            footer.write_u32::<LittleEndian>(tile.rows)?;
+            footer.write_u32::<LittleEndian>(tile.checksum)?;
+        }
+        footer.write_u64::<LittleEndian>(index.nrows as u64)?;
+        footer.write_u64::<LittleEndian>(index.tiles.len() as u64)?;
+        footer.write_u64::<LittleEndian>(footer_pos)?;
+        let checksum = crc32(&footer);
+        file.write_all(&footer)?;
+        file.write_u32::<LittleEndian>(checksum)?;
+        file.write_all(FOOTER_MAGIC)?;
+        file.flush()?;
+        // A short tile overwritten by a shorter one leaves stale bytes behind
+        let end = file.stream_position()?;
+        file.get_ref().set_len(end)?;
+
+        // Now re-open as read handle (flush+read)
+        let mut store = TileRowStore::open(self.temp_file.path(), self.column_types, &index)?;
+        store.temp_file = Some(self.temp_file);
+        store.spill = self.spill;
+
+        // Preload first/last tiles
+        if !store.tiles.is_empty() {
+            store.first_tile = store.load_tile_arc(0).ok();
+            store.last_tile = store.load_tile_arc(store.tiles.len() - 1).ok();
+        }
+
+        Ok(store)
+    }
 }
 
 /// To allow ResultsTab or tile cache to auto-clean up temp files:
@@ -223,5 +1261,178 @@ impl Drop for TileRowStore {
     fn drop(&mut self) {
         // NamedTempFile's Drop will remove the file
         // file is auto-closed
+        // the shared cache would otherwise hold the tiles until they age out
+        self.forget_tiles_from(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cells(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    /// Encode one column and read it back; returns the encoding it got
+    fn round_trip(ty: ColumnType, column: &[String]) -> u8 {
+        let refs: Vec<&str> = column.iter().map(String::as_str).collect();
+        let mut data = Vec::new();
+        TileRowStore::encode_column(&mut data, ty, &refs).unwrap();
+        let mut r = data.as_slice();
+        assert_eq!(TileRowStore::decode_column(&mut r, column.len()).unwrap(), column);
+        assert!(r.is_empty());
+        data[0]
+    }
+
+    /// The first column block of a tile of `rows`, with its bytes
+    fn first_block(types: &[ColumnType], rows: &[Vec<String>]) -> (ColumnBlock, Vec<u8>) {
+        let tile = TileRowStore::encode_tile(types, rows).unwrap();
+        let mut r = &tile[8..];
+        let block = ColumnBlock {
+            offset: tile_head_len(types.len()) as u64,
+            len: r.read_u32::<LittleEndian>().unwrap(),
+            data_len: r.read_u32::<LittleEndian>().unwrap(),
+            flags: r.read_u8().unwrap(),
+            checksum: r.read_u32::<LittleEndian>().unwrap(),
+        };
+        let bytes = tile[block.offset as usize..][..block.len as usize].to_vec();
+        (block, bytes)
+    }
+
+    #[test]
+    fn plain_columns_round_trip() {
+        let column: Vec<String> = (0..200).map(|i| (i * 7919).to_string()).collect();
+        assert_eq!(round_trip(ColumnType::Integer, &column), COL_PLAIN);
+    }
+
+    #[test]
+    fn runs_use_rle() {
+        let column: Vec<String> = (0..300).map(|i| ["OPEN", "CLOSED", "PENDING"][i / 100].to_string()).collect();
+        assert_eq!(round_trip(ColumnType::Text, &column), COL_RLE);
+    }
+
+    #[test]
+    fn repeated_values_use_a_dictionary() {
+        let column: Vec<String> = (0..300).map(|i| ["Netherlands", "Germany", "France"][i % 3].to_string()).collect();
+        assert_eq!(round_trip(ColumnType::Text, &column), COL_DICT);
+    }
+
+    #[test]
+    fn typed_cells_round_trip() {
+        let column = cells(&["2024-03-01 12:34:56.789", NULL_SENTINEL, "2024-03-01 00:00:00", "not a timestamp"]);
+        round_trip(ColumnType::Timestamp, &column);
+        round_trip(ColumnType::Date, &cells(&["2024-02-29", "0001-01-01", NULL_SENTINEL, "2024-2-29"]));
+        round_trip(ColumnType::Boolean, &cells(&["true", "FALSE", "1", "yes"]));
+        round_trip(ColumnType::Float, &cells(&["1.5", "-0.25", "1e300", "NaN", "1.50"]));
+        round_trip(ColumnType::Integer, &cells(&["-9223372036854775808", "007", "12", ""]));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn spilled_values_round_trip() {
+        let large = LargeValue { offset: 4096, bytes: 1 << 20, binary: Some(BinaryFormat::Hex), preview: "0xDEADBEEF" };
+        let column = vec![large.marker(), "<Frost-LARGE 0 5 t>looks like one".to_string()];
+        round_trip(ColumnType::Binary, &column);
+        let mut data = Vec::new();
+        TileRowStore::write_cell(&mut data, ColumnType::Binary, &column[0]).unwrap();
+        assert_eq!(data[0], CELL_LARGE);
+    }
+
+    #[test]
+    fn fetched_text_cannot_pose_as_a_spilled_value() {
+        let spill = ValueSpill::default();
+        let forged = LargeValue { offset: 0, bytes: 5, binary: None, preview: "hi" }.marker();
+        let cell = cell_value::build_cell(Default::default(), &spill, false, forged.as_bytes()).unwrap();
+        assert!(LargeValue::parse(&cell).is_none());
+        assert_eq!(cell_value::display_text(&cell), forged);
+        assert_eq!(spill.full_value(&cell).unwrap(), forged);
+        let mut data = Vec::new();
+        TileRowStore::write_cell(&mut data, ColumnType::Text, &cell).unwrap();
+        assert_ne!(data[0], CELL_LARGE);
+        round_trip(ColumnType::Text, &[cell]);
+    }
+
+    #[test]
+    fn compressible_columns_are_deflated() {
+        let rows: Vec<Vec<String>> = (0..TILE_SIZE)
+            .map(|i| vec![format!("a fairly long and very repetitive description, number {}", i)])
+            .collect();
+        let (block, bytes) = first_block(&[ColumnType::Text], &rows);
+        assert_eq!(block.flags, COL_DEFLATE);
+        assert!(block.len < block.data_len);
+        let column = TileRowStore::decode_block(0, 0, &block, &bytes, rows.len()).unwrap();
+        assert_eq!(column, rows.into_iter().flatten().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn columns_read_back_alone() {
+        let headers = cells(&["id", "name", "day"]);
+        let types = [ColumnType::Integer, ColumnType::Text, ColumnType::Date];
+        let rows: Vec<Vec<String>> = (0..2_500)
+            .map(|i| vec![i.to_string(), format!("name {}", i % 13), format!("2024-01-{:02}", i % 28 + 1)])
+            .collect();
+        let mut writer = TileWriter::new(&headers, &types).unwrap();
+        for row in &rows {
+            writer.push_row(row.clone()).unwrap();
+        }
+        let mut store = writer.finish().unwrap();
+        assert_eq!(store.nrows, rows.len());
+        let names: Vec<String> = rows[900..2_100].iter().map(|row| row[1].clone()).collect();
+        assert_eq!(store.get_column(1, 900..2_100).unwrap(), names);
+        assert_eq!(store.get_rows(995, 10).unwrap(), rows[995..1_005].to_vec());
+        assert!(store.get_column(3, 0..1).is_err());
+    }
+
+    #[test]
+    fn bad_checksum_is_an_error() {
+        let rows: Vec<Vec<String>> = (0..100).map(|i| vec![format!("row {}", i)]).collect();
+        let (block, mut bytes) = first_block(&[ColumnType::Text], &rows);
+        bytes[3] ^= 0x55;
+        assert!(TileRowStore::decode_block(0, 0, &block, &bytes, rows.len()).is_err());
+    }
+
+    #[test]
+    fn truncated_block_is_an_error() {
+        let column: Vec<String> = (0..100).map(|i| format!("row {}", i)).collect();
+        let refs: Vec<&str> = column.iter().map(String::as_str).collect();
+        let mut bytes = Vec::new();
+        TileRowStore::encode_column(&mut bytes, ColumnType::Text, &refs).unwrap();
+        bytes.truncate(bytes.len() / 2);
+        let block = ColumnBlock {
+            offset: 0,
+            len: bytes.len() as u32,
+            data_len: bytes.len() as u32,
+            flags: 0,
+            checksum: crc32(&bytes),
+        };
+        assert!(TileRowStore::decode_block(0, 0, &block, &bytes, column.len()).is_err());
+    }
+
+    #[test]
+    fn oversized_data_length_is_an_error() {
+        let rows: Vec<Vec<String>> = (0..TILE_SIZE).map(|i| vec![format!("repetitive text {}", i)]).collect();
+        let (mut block, bytes) = first_block(&[ColumnType::Text], &rows);
+        block.data_len = u32::MAX;
+        assert!(TileRowStore::decode_block(0, 0, &block, &bytes, rows.len()).is_err());
+    }
+
+    #[test]
+    fn bad_timestamp_digits_are_an_error() {
+        let mut cell = vec![CELL_TIMESTAMP];
+        cell.write_i64::<LittleEndian>(1_700_000_000).unwrap();
+        cell.write_u32::<LittleEndian>(123_000_000).unwrap();
+        cell.write_u8(12).unwrap();
+        assert!(TileRowStore::read_cell(&mut cell.as_slice()).is_err());
+        *cell.last_mut().unwrap() = 3;
+        assert_eq!(TileRowStore::read_cell(&mut cell.as_slice()).unwrap(), "2023-11-14 22:13:20.123");
+    }
+
+    #[test]
+    fn unknown_tags_and_short_cells_are_errors() {
+        assert!(TileRowStore::read_cell(&mut [42u8].as_slice()).is_err());
+        assert!(TileRowStore::read_cell(&mut [CELL_INT, 1, 2].as_slice()).is_err());
+        assert!(TileRowStore::read_cell(&mut [CELL_TEXT, 10, b'a'].as_slice()).is_err());
+        assert!(TileRowStore::decode_column(&mut [COL_RLE, 0].as_slice(), 5).is_err());
+        assert!(TileRowStore::decode_column(&mut [COL_DICT, 9].as_slice(), 5).is_err());
+    }
+}
@@ -1,5 +1,5 @@
-use crate::tile_rowstore::TileRowStore;
-use crate::{editor::Editor, results::{Results, ResultsContent}};
+use crate::tile_rowstore::{TileRowStore, TileWriter};
+use crate::{editor::Editor, results::{Results, ResultsContent, ResultsTab, Truncation}};
 use crate::syntax::{ParseState, step, Step};
 use crate::tile_rowstore::NULL_SENTINEL;
 use crate::batch_generator::BatchGeneratorDialog;
@@ -7,11 +7,17 @@ use crate::palette::STYLE;
 use crate::editor::GUTTER_WIDTH;
 use crate::db_tree::{DbTree, TreeAction};
 use crate::editor::normalize_text_for_terminal;
+use crate::query_backend::{backend_for, connect_backend, BackendError, ColumnInfo, Execution, QueryBackend, QueryCanceller, RowStream, SessionContext};
+use crate::config::Profile;
+use crate::column_type::ColumnType;
+use crate::bind_vars::{self, BindKind, BindValue};
+use crate::query_history::{fuzzy_score, CompletedStatement, HistoryEntry, QueryHistory};
+use crate::sql_error::{ErrorLocation, SqlError, StatementSource};
+use crate::cell_value::ValueOptions;
 
-use odbc::{create_environment_v3, Data, ResultSetState, Statement, Handle};
-use odbc::ffi::{SQLCancel, SQLHSTMT};   // raw FFI symbols live in `odbc::ffi`
 use std::{
     sync::{Arc, Mutex},
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
     sync::mpsc::{self, Receiver, Sender},
     thread,
     time::{Duration, Instant},
@@ -22,15 +28,39 @@ use crossterm::event::{KeyEvent, MouseEvent, KeyModifiers, KeyCode, KeyEventKind
 use anyhow::Result;
 use directories::UserDirs;
 use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 
 #[cfg(windows)]
 use windows_sys::Win32::System::Console::SetConsoleTitleW;
 
 const MIN_ROWS: i16 = 3;
+/// How often the row counter is refreshed while a result set is being fetched
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+/// Wait before the first automatic reconnect; doubles with every failed attempt
+const RECONNECT_BACKOFF: Duration = Duration::from_secs(2);
+/// Failed attempts after which the worker waits for a manual reconnect (F5)
+const MAX_RECONNECT_ATTEMPTS: u32 = 5;
+/// Connections a parallel run uses when Frost.toml doesn't say
+pub const DEFAULT_PARALLEL_CONNECTIONS: usize = 4;
+/// Finished tabs of earlier runs kept open; the oldest go first
+const MAX_KEPT_RESULT_TABS: usize = 20;
 
 #[derive(Debug)]
 pub enum DbWorkerRequest {
-    RunQueries(Vec<(String, String)>), // (query, context)
+    /// Statements run one after another, as (query, context). A failure skips
+    /// the rest unless `continue_on_error`; a cancel or lost connection always does.
+    RunQueries { queries: Vec<(String, String)>, continue_on_error: bool },
+    /// Like `RunQueries`, but the statements are independent and run side by
+    /// side on pooled connections; they complete in any order
+    RunParallel(Vec<(String, String)>),
+    /// Continue a result set that stopped at the row cap (`all` = no cap)
+    FetchMore { all: bool },
+    /// Drop the connection (if any) and connect again
+    Reconnect,
+    /// Autocommit off (`manual`) or back on
+    SetTransactionMode { manual: bool },
+    Commit,
+    Rollback,
     Cancel,
     Quit,
 }
@@ -38,9 +68,47 @@ pub enum DbWorkerRequest {
 #[derive(Debug)]
 pub enum DbWorkerResponse {
     Connected,
+    /// Connecting failed; the worker retries after `retry_in`, or waits for
+    /// a manual reconnect when it is `None`
+    Disconnected { message: String, retry_in: Option<Duration> },
+    Reconnecting { attempt: u32 },
+    /// Current role / warehouse / database / schema, after connecting and
+    /// after statements that change them
+    SessionContext(SessionContext),
+    TransactionModeChanged { manual: bool },
+    TransactionEnded { committed: bool },
+    TransactionError { message: String },
     QueryStarted { query_idx: usize, started: Instant, query_context: String },
-    QueryFinished { query_idx: usize, elapsed: Duration, result: ResultsContent },
-    QueryError { query_idx: usize, elapsed: Duration, message: String },
+    /// Rows are still arriving; `result` is a live view of what's on disk so far
+    QueryStreaming { query_idx: usize, result: ResultsContent },
+    QueryProgress { query_idx: usize, rows_fetched: usize },
+    /// The statement's current result set is complete and another one
+    /// follows: `result` fills the current tab, the next set gets a new one
+    QueryNextResultSet { query_idx: usize, result: ResultsContent, query_id: Option<String> },
+    /// `query_id` is the server's id for the statement, when the backend has one
+    QueryFinished { query_idx: usize, elapsed: Duration, result: ResultsContent, query_id: Option<String> },
+    /// Cancelled by the statement timeout
+    QueryTimedOut { query_idx: usize, elapsed: Duration, timeout: Duration },
+    /// Fetching stopped at the row cap; `resumable` if the cursor is still open
+    QueryTruncated { query_idx: usize, elapsed: Duration, result: ResultsContent, resumable: bool, query_id: Option<String> },
+    QueryError { query_idx: usize, elapsed: Duration, message: String, query_id: Option<String> },
+    /// A parked cursor was closed because another request came in. The UI
+    /// has moved on by then, so `result` goes to the tab whose live view
+    /// reads the tile file at `path`
+    ParkedSealed { path: PathBuf, result: ResultsContent },
+    /// Done with a run or a fetch-more; the next request starts right away
+    Idle,
+}
+
+/// What the path typed into a [`SaveAsDialog`] is for
+#[derive(Clone, Copy, PartialEq)]
+pub enum PathPurpose {
+    /// Ctrl+Shift+S: save the editor buffer
+    SaveSql,
+    /// w in the results pane: save the current tab as a `.frostres` file
+    SaveResult,
+    /// Ctrl+O: open a `.frostres` file into a new tab
+    OpenResult,
 }
 
 pub struct SaveAsDialog {
@@ -48,9 +116,21 @@ pub struct SaveAsDialog {
     pub file_path: String,
     pub cursor_pos: usize,
     pub message: Option<String>,
+    pub purpose: PathPurpose,
 }
 
 impl SaveAsDialog {
+    /// Prefilled with `path`, for saving or opening a result file
+    pub fn for_result(purpose: PathPurpose, path: PathBuf) -> Self {
+        let file_path = path.to_string_lossy().to_string();
+        Self {
+            cursor_pos: file_path.len(),
+            file_path,
+            purpose,
+            ..Self::new()
+        }
+    }
+
     pub fn new() -> Self {
         // Get current directory and add trailing slash
         let current_dir = std::env::current_dir()
@@ -69,6 +149,7 @@ impl SaveAsDialog {
             file_path: file_path.clone(),
             cursor_pos: file_path.len(),
             message: None,
+            purpose: PathPurpose::SaveSql,
         }
     }
     
@@ -140,12 +221,368 @@ impl SaveAsDialog {
     }
 }
 
-#[derive(Clone, Copy)]
-pub struct SafeStmt(SQLHSTMT);
-unsafe impl Send for SafeStmt {}
-unsafe impl Sync for SafeStmt {}
 
 
+/// F2 picker over the `[profiles.<name>]` entries in Frost.toml
+pub struct ProfilePickerDialog {
+    pub active: bool,
+    pub names: Vec<String>,
+    pub selected: usize,
+}
+
+impl ProfilePickerDialog {
+    pub fn new(names: Vec<String>, current: Option<&str>) -> Self {
+        let selected = current
+            .and_then(|c| names.iter().position(|n| n == c))
+            .unwrap_or(0);
+        Self {
+            active: true,
+            names,
+            selected,
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<String> {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if self.selected + 1 < self.names.len() {
+                    self.selected += 1;
+                }
+            }
+            KeyCode::Home => {
+                self.selected = 0;
+            }
+            KeyCode::End => {
+                self.selected = self.names.len().saturating_sub(1);
+            }
+            KeyCode::Enter => {
+                self.active = false;
+                return self.names.get(self.selected).cloned();
+            }
+            KeyCode::Esc => {
+                self.active = false;
+            }
+            _ => {}
+        }
+        None
+    }
+}
+
+/// Masked entry for the `${prompt:…}` placeholders of the connection string
+pub struct SecretPromptDialog {
+    pub labels: Vec<String>,
+    values: Vec<String>,
+    pub selected: usize,
+}
+
+impl SecretPromptDialog {
+    pub fn new(labels: Vec<String>) -> Self {
+        let values = vec![String::new(); labels.len()];
+        Self { labels, values, selected: 0 }
+    }
+
+    /// `Some(true)` once submitted, `Some(false)` when cancelled
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<bool> {
+        match key.code {
+            KeyCode::Char(ch) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.values[self.selected].push(ch);
+            }
+            KeyCode::Backspace => {
+                self.values[self.selected].pop();
+            }
+            KeyCode::Up | KeyCode::BackTab => {
+                self.selected = (self.selected + self.labels.len() - 1) % self.labels.len();
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.selected = (self.selected + 1) % self.labels.len();
+            }
+            KeyCode::Enter if self.selected + 1 < self.labels.len() => {
+                self.selected += 1;
+            }
+            KeyCode::Enter => {
+                for (label, value) in self.labels.iter().zip(self.values.drain(..)) {
+                    crate::secrets::set_prompt(label, value);
+                }
+                return Some(true);
+            }
+            KeyCode::Esc => return Some(false),
+            _ => {}
+        }
+        None
+    }
+}
+
+/// What the cell inspector asks the workspace to do
+pub enum InspectorAction {
+    Close,
+    Copy,
+}
+
+/// `v` in the results pane: the complete value of the cell under the cursor,
+/// hard-wrapped and scrollable
+pub struct CellInspector {
+    pub title: String,
+    text: String,
+    lines: Vec<String>,
+    pub scroll: usize,
+    page: usize,
+}
+
+impl CellInspector {
+    /// `screen` is the terminal size; the inspector wraps to the width it
+    /// will be drawn at
+    pub fn new(title: String, text: String, screen: Rect) -> Self {
+        let inner = Self::area(screen);
+        let width = inner.width.saturating_sub(2).max(1) as usize;
+        let mut lines = Vec::new();
+        for line in text.lines() {
+            let chars: Vec<char> = line.chars().collect();
+            if chars.is_empty() {
+                lines.push(String::new());
+            }
+            lines.extend(chars.chunks(width).map(|chunk| chunk.iter().collect::<String>()));
+        }
+        let page = inner.height.saturating_sub(3).max(1) as usize;
+        Self { title, text, lines, scroll: 0, page }
+    }
+
+    /// Where the inspector is drawn on a `screen`-sized terminal
+    fn area(screen: Rect) -> Rect {
+        Rect {
+            x: 4.min(screen.width / 8),
+            y: 2.min(screen.height / 8),
+            width: screen.width - 2 * 4.min(screen.width / 8),
+            height: screen.height - 2 * 2.min(screen.height / 8),
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<InspectorAction> {
+        let last = self.lines.len().saturating_sub(self.page);
+        match key.code {
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q' | 'v') => return Some(InspectorAction::Close),
+            KeyCode::Char('c') => return Some(InspectorAction::Copy),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll = (self.scroll + 1).min(last),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(self.page),
+            KeyCode::PageDown => self.scroll = (self.scroll + self.page).min(last),
+            KeyCode::Home | KeyCode::Char('g') => self.scroll = 0,
+            KeyCode::End | KeyCode::Char('G') => self.scroll = last,
+            _ => {}
+        }
+        None
+    }
+}
+
+/// Form for the `:name` / `$name` / `@name` placeholders of a run
+pub struct BindVariablesDialog {
+    pub active: bool,
+    pub names: Vec<String>,
+    pub values: Vec<BindValue>,
+    pub selected: usize,
+    pub cursor_pos: usize,      // in chars, within the selected value
+    pub message: Option<String>,
+    pub queries: Vec<String>,   // statements waiting for the values
+    pub sources: Vec<Option<StatementSource>>,  // where they are in the editor
+    pub parallel: bool,         // run them on pooled connections (F4)
+    backslash_escapes: bool,
+}
+
+impl BindVariablesDialog {
+    pub fn new(names: Vec<String>, remembered: &HashMap<String, BindValue>, queries: Vec<String>, backslash_escapes: bool) -> Self {
+        let values: Vec<BindValue> = names
+            .iter()
+            .map(|n| remembered.get(n).cloned().unwrap_or_default())
+            .collect();
+        let cursor_pos = values.first().map(|v| v.text.chars().count()).unwrap_or(0);
+        Self {
+            active: true,
+            names,
+            values,
+            selected: 0,
+            cursor_pos,
+            message: None,
+            queries,
+            sources: Vec::new(),
+            parallel: false,
+            backslash_escapes,
+        }
+    }
+
+    fn select(&mut self, row: usize) {
+        self.selected = row;
+        self.cursor_pos = self.values[row].text.chars().count();
+    }
+
+    fn byte_pos(&self) -> usize {
+        let text = &self.values[self.selected].text;
+        text.char_indices().nth(self.cursor_pos).map(|(b, _)| b).unwrap_or(text.len())
+    }
+
+    /// Returns the literal for each placeholder that gets one once the form is submitted
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<HashMap<String, String>> {
+        match key.code {
+            KeyCode::Char('t') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                let value = &mut self.values[self.selected];
+                value.kind = value.kind.next();
+            }
+            KeyCode::Char(ch) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                let at = self.byte_pos();
+                self.values[self.selected].text.insert(at, ch);
+                self.cursor_pos += 1;
+            }
+            KeyCode::Backspace => {
+                if self.cursor_pos > 0 {
+                    self.cursor_pos -= 1;
+                    let at = self.byte_pos();
+                    self.values[self.selected].text.remove(at);
+                }
+            }
+            KeyCode::Delete => {
+                if self.cursor_pos < self.values[self.selected].text.chars().count() {
+                    let at = self.byte_pos();
+                    self.values[self.selected].text.remove(at);
+                }
+            }
+            KeyCode::Left => {
+                self.cursor_pos = self.cursor_pos.saturating_sub(1);
+            }
+            KeyCode::Right => {
+                if self.cursor_pos < self.values[self.selected].text.chars().count() {
+                    self.cursor_pos += 1;
+                }
+            }
+            KeyCode::Home => {
+                self.cursor_pos = 0;
+            }
+            KeyCode::End => {
+                self.cursor_pos = self.values[self.selected].text.chars().count();
+            }
+            KeyCode::Up | KeyCode::BackTab => {
+                self.select((self.selected + self.names.len() - 1) % self.names.len());
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.select((self.selected + 1) % self.names.len());
+            }
+            KeyCode::Enter => {
+                let mut literals = HashMap::new();
+                for (i, (name, value)) in self.names.iter().zip(&self.values).enumerate() {
+                    match value.to_literal(self.backslash_escapes) {
+                        Ok(Some(lit)) => {
+                            literals.insert(name.clone(), lit);
+                        }
+                        Ok(None) => {}
+                        Err(e) => {
+                            self.message = Some(format!("{}: {}", name, e));
+                            self.select(i);
+                            return None;
+                        }
+                    }
+                }
+                self.active = false;
+                return Some(literals);
+            }
+            KeyCode::Esc => {
+                self.active = false;
+            }
+            _ => {}
+        }
+        None
+    }
+}
+
+/// F3 browser over the query history, filtered as you type
+pub struct HistoryBrowser {
+    pub active: bool,
+    pub entries: Vec<HistoryEntry>,   // newest first
+    pub query: String,
+    pub matches: Vec<usize>,          // indices into `entries`, best match first
+    pub selected: usize,
+}
+
+pub enum HistoryAction {
+    Insert(String),
+    Run(String),
+}
+
+impl HistoryBrowser {
+    pub fn new(entries: Vec<HistoryEntry>) -> Self {
+        let mut browser = Self {
+            active: true,
+            entries,
+            query: String::new(),
+            matches: Vec::new(),
+            selected: 0,
+        };
+        browser.refilter();
+        browser
+    }
+
+    fn refilter(&mut self) {
+        if self.query.trim().is_empty() {
+            self.matches = (0..self.entries.len()).collect();
+        } else {
+            let mut scored: Vec<(i64, usize)> = self.entries
+                .iter()
+                .enumerate()
+                .filter_map(|(i, e)| {
+                    let haystack = format!("{} {}", e.sql, e.query_context);
+                    fuzzy_score(&self.query, &haystack).map(|score| (score, i))
+                })
+                .collect();
+            scored.sort_by(|a, b| b.0.cmp(&a.0));   // stable, so ties stay newest first
+            self.matches = scored.into_iter().map(|(_, i)| i).collect();
+        }
+        self.selected = 0;
+    }
+
+    pub fn selected_entry(&self) -> Option<&HistoryEntry> {
+        self.matches.get(self.selected).map(|&i| &self.entries[i])
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<HistoryAction> {
+        match key.code {
+            KeyCode::Enter => {
+                let sql = self.selected_entry()?.sql.clone();
+                self.active = false;
+                if key.modifiers.contains(KeyModifiers::CONTROL) {
+                    return Some(HistoryAction::Run(sql));
+                }
+                return Some(HistoryAction::Insert(sql));
+            }
+            KeyCode::Char(ch) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.query.push(ch);
+                self.refilter();
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.refilter();
+            }
+            KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                if self.selected + 1 < self.matches.len() {
+                    self.selected += 1;
+                }
+            }
+            KeyCode::PageUp => {
+                self.selected = self.selected.saturating_sub(10);
+            }
+            KeyCode::PageDown => {
+                self.selected = (self.selected + 10).min(self.matches.len().saturating_sub(1));
+            }
+            KeyCode::Esc | KeyCode::F(3) => {
+                self.active = false;
+            }
+            _ => {}
+        }
+        None
+    }
+}
+
 pub struct Workspace {
     pub db_tree: DbTree,
     pub editor: Editor,
@@ -155,8 +592,28 @@ pub struct Workspace {
     pub run_started: Option<Instant>,
     pub run_duration: Option<Duration>,
     pub running_query_idx: Option<usize>,
+    /// Role, warehouse, database and schema the worker's session is using
+    pub session: Option<SessionContext>,
+    /// Keep running a serial run's later statements after one fails (F12)
+    pub continue_on_error: bool,
+    run_continues: bool,                // `continue_on_error` as of the current run
+    run_summary: Option<String>,        // "27 ok / 3 failed" once a run is over
+    parallel_remaining: Option<usize>,  // statements of a parallel run still out
+    worker_busy: bool,                  // a run or fetch-more was sent and the worker isn't idle yet
+    run_queue: VecDeque<QueuedRun>,     // runs waiting for the worker, shown as the last tabs
     pub batch_generator: Option<BatchGeneratorDialog>,
     pub save_as_dialog: Option<SaveAsDialog>,
+    pub profile_picker: Option<ProfilePickerDialog>,
+    pub bind_dialog: Option<BindVariablesDialog>,
+    pub secret_prompt: Option<SecretPromptDialog>,
+    pub cell_inspector: Option<CellInspector>,
+    connection_string: String,  // as configured, placeholders unexpanded
+    bind_values: HashMap<String, BindValue>,  // last value entered per placeholder
+    pub history_browser: Option<HistoryBrowser>,
+    history: Option<QueryHistory>,            // None if the database couldn't be opened
+    run_statements: Vec<(String, String)>,    // (statement, context) of the current run, unwrapped
+    run_sources: Vec<Option<StatementSource>>, // editor position per statement, for error jumps
+    history_ids: Vec<Option<i64>>,            // history row per statement, once recorded
 
     pub last_editor_area: Option<Rect>,
     pub last_results_area: Option<Rect>,
@@ -176,7 +633,16 @@ pub struct Workspace {
 
     pub db_req_tx: Sender<DbWorkerRequest>,
     pub db_resp_rx: Receiver<DbWorkerResponse>,
-    current_stmt: Arc<Mutex<Option<SafeStmt>>>,
+    /// Workers replaced by a profile switch, read until they exit in case
+    /// one hands back a parked result
+    old_resp_rxs: Vec<Receiver<DbWorkerResponse>>,
+    canceller: Arc<dyn QueryCanceller>,
+    wrap_script_blocks: bool,  // Snowflake needs EXECUTE IMMEDIATE around bare blocks
+    backslash_escapes: bool,   // quote bind values for the backend's string syntax
+    worker_options: WorkerOptions, // reused when the worker is restarted
+
+    pub profiles: BTreeMap<String, Profile>,
+    pub active_profile: Option<String>,  // None = top-level connection_string
 
     pub total_queries: usize,
 
@@ -186,10 +652,54 @@ pub struct Workspace {
     results_hidden: bool,
     editor_hidden: bool,
     drag_source: Option<Focus>,  // Track which pane started a drag
-    pub connected: bool,  // Track Snowflake connection status
+    pub connection: ConnectionState,  // shown in the status bar
+    /// Manual-commit mode: statements run since the last commit/rollback
+    pub transaction: Option<usize>,
+    txn_prompt: Option<AfterTransaction>,     // "open transaction" dialog shown
+    after_transaction: Option<AfterTransaction>, // waiting for the commit/rollback
+    pub exit_ready: bool,                     // checked by the main loop
     pub frame_counter: u32,
 }
 
+/// Connection status as last reported by the DB worker
+#[derive(Debug, Clone)]
+pub enum ConnectionState {
+    Connecting,
+    Connected,
+    Reconnecting { attempt: u32 },
+    /// `retry_at` is when the worker tries again on its own, if it will
+    Disconnected { message: String, retry_at: Option<Instant> },
+}
+
+impl ConnectionState {
+    fn label(&self) -> String {
+        match self {
+            ConnectionState::Connecting => "[Not Connected]".to_string(),
+            ConnectionState::Connected => "[Connected]".to_string(),
+            ConnectionState::Reconnecting { attempt } => format!("[Reconnecting… #{}]", attempt),
+            ConnectionState::Disconnected { retry_at: Some(at), .. } => {
+                format!("[Disconnected – retry in {}s]", at.saturating_duration_since(Instant::now()).as_secs())
+            }
+            ConnectionState::Disconnected { retry_at: None, .. } => "[Disconnected – F5 to reconnect]".to_string(),
+        }
+    }
+}
+
+/// Statements submitted while the worker was busy
+#[derive(Debug, Clone)]
+struct QueuedRun {
+    queries: Vec<String>,
+    sources: Vec<Option<StatementSource>>,
+    parallel: bool,
+}
+
+/// What was interrupted by the "open transaction" prompt
+#[derive(Debug, Clone)]
+enum AfterTransaction {
+    Exit,
+    SwitchProfile(String),
+}
+
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub enum Focus {
     Editor,
@@ -199,269 +709,1056 @@ pub enum Focus {
 
 
 
-pub fn start_db_worker(
-    conn_str: String,
-) -> (
-    Sender<DbWorkerRequest>,
-    Receiver<DbWorkerResponse>,
-    Arc<Mutex<Option<SafeStmt>>>
-) {
-    let (req_tx,  req_rx)  = mpsc::channel();
-    let (resp_tx, resp_rx) = mpsc::channel();
+/// Settings the DB worker applies to every statement
+#[derive(Debug, Clone, Default)]
+pub struct WorkerOptions {
+    /// Stop fetching a result set after this many rows (`--@limit N` overrides)
+    pub fetch_limit: Option<usize>,
+    /// Cancel statements that run longer than this (`--@timeout 30s` overrides)
+    pub statement_timeout: Option<Duration>,
+    /// Connections a parallel run may use, the worker's own included
+    pub parallel_connections: usize,
+    /// Binary format and the size past which cells are only previewed
+    pub values: ValueOptions,
+}
+
+/// Cancels the running statement once it outlives its timeout.
+/// Dropping it disarms the timer.
+struct Watchdog {
+    _done: Option<Sender<()>>,
+    fired: Arc<AtomicBool>,
+}
 
-    // shared handle of the statement that is *currently executing*
-    let current_stmt: Arc<Mutex<Option<SafeStmt>>> =
-        Arc::new(Mutex::new(None));
+impl Watchdog {
+    fn arm(timeout: Option<Duration>, canceller: &Arc<dyn QueryCanceller>) -> Self {
+        let fired = Arc::new(AtomicBool::new(false));
+        let Some(timeout) = timeout else {
+            return Self { _done: None, fired };
+        };
 
-    // clone into the worker-thread
-    let thread_stmt = Arc::clone(&current_stmt);
-    thread::spawn(move || {
-        // Try to create environment
-        let env = match create_environment_v3() {
-            Ok(env) => env,
-            Err(_) => {
-                // Don't exit - keep thread alive but not connected
-                loop {
-                    match req_rx.recv() {
-                        Ok(DbWorkerRequest::Quit) | Err(_) => break,
-                        _ => {
-                            // Ignore other requests when not connected
-                            continue;
-                        }
+        let (done_tx, done_rx) = mpsc::channel::<()>();
+        let canceller = Arc::clone(canceller);
+        let flag = Arc::clone(&fired);
+        thread::spawn(move || {
+            // the sender is dropped (Disconnected) when the statement ends in time
+            if let Err(mpsc::RecvTimeoutError::Timeout) = done_rx.recv_timeout(timeout) {
+                flag.store(true, Ordering::SeqCst);
+                canceller.cancel();
+            }
+        });
+        Self { _done: Some(done_tx), fired }
+    }
+
+    fn fired(&self) -> bool {
+        self.fired.load(Ordering::SeqCst)
+    }
+}
+
+/// Why `ResultFetch::fetch` returned
+enum FetchStop {
+    Exhausted,
+    Capped,
+    FetchError(BackendError),
+    StoreError(std::io::Error),
+}
+
+/// A result set being copied from the backend cursor into a tile file
+struct ResultFetch<'s> {
+    query_idx: usize,
+    query_id: Option<String>,
+    rows: Box<dyn RowStream + 's>,
+    headers: Vec<String>,
+    column_types: Vec<ColumnType>,
+    writer: TileWriter,
+    streaming: bool,
+}
+
+impl<'s> ResultFetch<'s> {
+    fn new(query_idx: usize, rows: Box<dyn RowStream + 's>) -> std::io::Result<Self> {
+        let headers: Vec<String> = rows.columns().iter().map(|c| c.name.clone()).collect();
+        let column_types: Vec<ColumnType> = rows.columns().iter().map(|c| c.col_type).collect();
+        let mut writer = TileWriter::new(&headers, &column_types)?;
+        writer.set_spill(rows.value_spill());
+        let query_id = rows.query_id();
+        Ok(Self { query_idx, query_id, rows, headers, column_types, writer, streaming: false })
+    }
+
+    fn table(&self, tile_store: TileRowStore) -> ResultsContent {
+        ResultsContent::Table {
+            headers: self.headers.clone(),
+            column_types: self.column_types.clone(),
+            tile_store,
+        }
+    }
+
+    /// Pull up to `max_rows` more rows (all of them for `None`)
+    fn fetch(&mut self, max_rows: Option<usize>, resp_tx: &Sender<DbWorkerResponse>) -> FetchStop {
+        let mut fetched = 0usize;
+        let mut last_progress = Instant::now();
+        loop {
+            if max_rows.map_or(false, |max| fetched >= max) {
+                return FetchStop::Capped;
+            }
+            let row = match self.rows.next_row() {
+                Ok(Some(row)) => row,
+                Ok(None) => {
+                    let next = self.rows.next_result_set();
+                    let columns = match &next {
+                        Ok(true) => self.rows.columns().to_vec(),
+                        Ok(false) => return FetchStop::Exhausted,
+                        // the error goes to a tab of its own, after the sets that did arrive
+                        Err(_) => Vec::new(),
+                    };
+                    if let Err(e) = self.next_set(&columns, resp_tx) {
+                        return FetchStop::StoreError(e);
+                    }
+                    if let Err(e) = next {
+                        return FetchStop::FetchError(e);
                     }
+                    fetched = 0;  // the row cap applies to each result set
+                    continue;
+                }
+                Err(e) => return FetchStop::FetchError(e),
+            };
+            fetched += 1;
+            let row = row.into_iter()
+                .map(|val| val.unwrap_or_else(|| NULL_SENTINEL.to_string()))
+                .collect();
+            let tile_done = match self.writer.push_row(row) {
+                Ok(done) => done,
+                Err(e) => return FetchStop::StoreError(e),
+            };
+
+            // 📡 first tile on disk: hand the UI a live view it can scroll
+            if tile_done && !self.streaming {
+                if let Ok(view) = self.writer.live_view() {
+                    let _ = resp_tx.send(DbWorkerResponse::QueryStreaming {
+                        query_idx: self.query_idx,
+                        result: self.table(view),
+                    });
+                    self.streaming = true;
                 }
-                return;
             }
+            if tile_done || last_progress.elapsed() >= PROGRESS_INTERVAL {
+                let _ = resp_tx.send(DbWorkerResponse::QueryProgress {
+                    query_idx: self.query_idx,
+                    rows_fetched: self.writer.rows_written(),
+                });
+                last_progress = Instant::now();
+            }
+        }
+    }
+
+    /// Seal the current result set and start writing the next one
+    fn next_set(&mut self, columns: &[ColumnInfo], resp_tx: &Sender<DbWorkerResponse>) -> std::io::Result<()> {
+        let headers: Vec<String> = columns.iter().map(|c| c.name.clone()).collect();
+        let column_types: Vec<ColumnType> = columns.iter().map(|c| c.col_type).collect();
+        let mut writer = TileWriter::new(&headers, &column_types)?;
+        writer.set_spill(self.rows.value_spill());
+        let tile_store = std::mem::replace(&mut self.writer, writer).finish()?;
+        let headers = std::mem::replace(&mut self.headers, headers);
+        let column_types = std::mem::replace(&mut self.column_types, column_types);
+        self.streaming = false;
+        let _ = resp_tx.send(DbWorkerResponse::QueryNextResultSet {
+            query_idx: self.query_idx,
+            result: ResultsContent::Table { headers, column_types, tile_store },
+            query_id: self.query_id.clone(),
+        });
+        Ok(())
+    }
+
+    /// Result set fully read
+    fn finish(self, elapsed: Duration, resp_tx: &Sender<DbWorkerResponse>) {
+        let (query_idx, query_id) = (self.query_idx, self.query_id);
+        let (headers, column_types) = (self.headers, self.column_types);
+        let _ = resp_tx.send(match self.writer.finish() {
+            Ok(tile_store) => DbWorkerResponse::QueryFinished {
+                query_idx,
+                elapsed,
+                result: ResultsContent::Table { headers, column_types, tile_store },
+                query_id,
+            },
+            Err(e) => DbWorkerResponse::QueryError {
+                query_idx,
+                elapsed,
+                message: format!("TileRowStore error: {e:?}"),
+                query_id,
+            },
+        });
+    }
+
+    /// Stopped at the cap and the cursor is being dropped
+    fn seal(self, elapsed: Duration, resp_tx: &Sender<DbWorkerResponse>) {
+        let (query_idx, query_id) = (self.query_idx, self.query_id);
+        let (headers, column_types) = (self.headers, self.column_types);
+        let _ = resp_tx.send(match self.writer.finish() {
+            Ok(tile_store) => DbWorkerResponse::QueryTruncated {
+                query_idx,
+                elapsed,
+                result: ResultsContent::Table { headers, column_types, tile_store },
+                resumable: false,
+                query_id,
+            },
+            Err(e) => DbWorkerResponse::QueryError {
+                query_idx,
+                elapsed,
+                message: format!("TileRowStore error: {e:?}"),
+                query_id,
+            },
+        });
+    }
+
+    /// Parked at the cap and the cursor is being dropped for another request
+    fn seal_parked(self, resp_tx: &Sender<DbWorkerResponse>) {
+        let path = self.writer.path().to_path_buf();
+        let result = match self.writer.finish() {
+            Ok(tile_store) => ResultsContent::Table { headers: self.headers, column_types: self.column_types, tile_store },
+            Err(e) => ResultsContent::Error {
+                message: format!("TileRowStore error: {e:?}"),
+                cursor: 0,
+                selection: None,
+                location: None,
+            },
         };
-        
-        // Try to connect
-        let conn = match env.connect_with_connection_string(&conn_str) {
-            Ok(conn) => {
-                // Signal successful connection
-                let _ = resp_tx.send(DbWorkerResponse::Connected);
-                
-                // Enable all secondary roles by default
-                if let Ok(stmt) = Statement::with_parent(&conn) {
-                    let _ = stmt.exec_direct("USE SECONDARY ROLES ALL");
+        let _ = resp_tx.send(DbWorkerResponse::ParkedSealed { path, result });
+    }
+
+    /// Stopped at the cap with the cursor kept open for `FetchMore`
+    fn park(&mut self, elapsed: Duration, resp_tx: &Sender<DbWorkerResponse>) {
+        let view = self.writer.publish_partial().and_then(|_| self.writer.live_view());
+        let _ = resp_tx.send(match view {
+            Ok(view) => DbWorkerResponse::QueryTruncated {
+                query_idx: self.query_idx,
+                elapsed,
+                result: self.table(view),
+                resumable: true,
+                query_id: self.query_id.clone(),
+            },
+            Err(e) => DbWorkerResponse::QueryError {
+                query_idx: self.query_idx,
+                elapsed,
+                message: format!("TileRowStore error: {e:?}"),
+                query_id: self.query_id.clone(),
+            },
+        });
+    }
+}
+
+/// How `run_statement` left a statement
+enum StatementEnd<'s> {
+    /// Reported to the UI; later statements still run
+    Done,
+    /// Stopped at the row cap with the cursor still open
+    Capped { fetch: ResultFetch<'s>, started: Instant, limit: Option<usize>, timeout: Option<Duration> },
+    /// Failed, timed out or was cancelled (already reported)
+    Failed { connection_lost: bool },
+}
+
+/// Execute one statement and fetch its rows up to the row cap. Everything is
+/// reported to the UI except a capped result, which the caller parks or seals.
+fn run_statement<'s, B: QueryBackend + ?Sized>(
+    backend: &'s mut B,
+    canceller: &Arc<dyn QueryCanceller>,
+    options: &WorkerOptions,
+    query_idx: usize,
+    (query, context): &(String, String),
+    resp_tx: &Sender<DbWorkerResponse>,
+) -> StatementEnd<'s> {
+    let i = query_idx;
+    let started = Instant::now();
+    let _ = resp_tx.send(DbWorkerResponse::QueryStarted { query_idx: i, started, query_context: context.clone() });
+
+    let hints = Workspace::statement_timeout_hint(query)
+        .and_then(|timeout| Ok((timeout, Workspace::fetch_limit_hint(query)?)));
+    let (timeout_hint, limit_hint) = match hints {
+        Ok(hints) => hints,
+        Err(message) => {
+            let _ = resp_tx.send(DbWorkerResponse::QueryError {
+                query_idx: i,
+                elapsed: started.elapsed(),
+                message,
+                query_id: None,
+            });
+            return StatementEnd::Failed { connection_lost: false };
+        }
+    };
+    let timeout = match timeout_hint {
+        Some(t) if t.is_zero() => None,
+        Some(t) => Some(t),
+        None => options.statement_timeout,
+    };
+    let watchdog = Watchdog::arm(timeout, canceller);
+    let timed_out = |watchdog: &Watchdog, elapsed: Duration| {
+        watchdog.fired().then(|| DbWorkerResponse::QueryTimedOut {
+            query_idx: i,
+            elapsed,
+            timeout: timeout.unwrap_or_default(),
+        })
+    };
+
+    // Execute the SQL text.
+    match backend.execute(query) {
+        Ok(Execution::Rows(rows)) => {
+            let limit = match limit_hint {
+                Some(0) => None,
+                Some(n) => Some(n),
+                None => options.fetch_limit,
+            };
+            let query_id = rows.query_id();
+            let mut fetch = match ResultFetch::new(i, rows) {
+                Ok(f) => f,
+                Err(e) => {
+                    let msg = format!("TileRowStore error: {e:?}");
+                    let _ = resp_tx.send(DbWorkerResponse::QueryError {
+                        query_idx: i,
+                        elapsed: started.elapsed(),
+                        message: msg,
+                        query_id,
+                    });
+                    return StatementEnd::Failed { connection_lost: false };
                 }
-                
-                conn
-            }
-            Err(_) => {
-                // Connection failed - keep thread alive but not connected
-                // Don't send an error response here - let the UI handle the "Not Connected" state
-                loop {
-                    match req_rx.recv() {
-                        Ok(DbWorkerRequest::Quit) | Err(_) => break,
-                        _ => {
-                            // Ignore other requests when not connected
-                            continue;
+            };
+
+            match fetch.fetch(limit, resp_tx) {
+                FetchStop::Exhausted => {
+                    fetch.finish(started.elapsed(), resp_tx);
+                    StatementEnd::Done
+                }
+                FetchStop::Capped => StatementEnd::Capped { fetch, started, limit, timeout },
+                FetchStop::FetchError(e) => {
+                    let _ = resp_tx.send(timed_out(&watchdog, started.elapsed()).unwrap_or_else(|| {
+                        DbWorkerResponse::QueryError {
+                            query_idx: i,
+                            elapsed: started.elapsed(),
+                            message: format!("Fetch Error: {}", e),
+                            query_id: fetch.query_id.clone(),
                         }
+                    }));
+                    StatementEnd::Failed { connection_lost: e.is_connection_lost() }
+                }
+                FetchStop::StoreError(e) => {
+                    let _ = resp_tx.send(DbWorkerResponse::QueryError {
+                        query_idx: i,
+                        elapsed: started.elapsed(),
+                        message: format!("TileRowStore error: {e:?}"),
+                        query_id: fetch.query_id.clone(),
+                    });
+                    StatementEnd::Failed { connection_lost: false }
+                }
+            }
+        }
+        Ok(Execution::NoRows { affected_rows, query_id }) => {
+            let msg = match affected_rows {
+                Some(cnt) if cnt > 0 => {
+                    format!("Statement affected {} row{}", cnt, if cnt == 1 { "" } else { "s" })
+                }
+                Some(_) => "Statement executed successfully (no rows affected).".to_string(),
+                None => "Statement executed successfully.".to_string(),
+            };
+            let _ = resp_tx.send(DbWorkerResponse::QueryFinished {
+                query_idx: i,
+                elapsed: started.elapsed(),
+                result: ResultsContent::Info { message: msg },
+                query_id,
+            });
+            StatementEnd::Done
+        }
+        Err(e) => {
+            // Could be the statement timeout, user-cancelled (HY008),
+            // a dropped connection (08xxx) or some other error
+            let msg = format!("Execution Error: {}", e);
+            let _ = resp_tx.send(timed_out(&watchdog, started.elapsed()).unwrap_or(
+                DbWorkerResponse::QueryError {
+                    query_idx: i,
+                    elapsed: started.elapsed(),
+                    message: msg,
+                    query_id: e.query_id.clone(),
+                }
+            ));
+            StatementEnd::Failed { connection_lost: e.is_connection_lost() }
+        }
+    }
+}
+
+/// The handle the UI cancels with. Besides the worker's own statement it
+/// reaches every pooled connection of a parallel run and stops the run from
+/// starting the statements still queued.
+struct RunCanceller {
+    main: Arc<dyn QueryCanceller>,
+    pooled: Mutex<Vec<Arc<dyn QueryCanceller>>>,
+    cancelled: AtomicBool,
+}
+
+impl QueryCanceller for RunCanceller {
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.main.cancel();
+        for canceller in self.pooled.lock().unwrap().iter() {
+            canceller.cancel();
+        }
+    }
+}
+
+/// Work through the shared statement queue on one connection. Returns true
+/// if that connection was lost.
+fn drain_queue<B: QueryBackend + ?Sized>(
+    backend: &mut B,
+    canceller: &Arc<dyn QueryCanceller>,
+    next: &AtomicUsize,
+    cancelled: &AtomicBool,
+    options: &WorkerOptions,
+    queries: &[(String, String)],
+    resp_tx: &Sender<DbWorkerResponse>,
+) -> bool {
+    while !cancelled.load(Ordering::SeqCst) {
+        let i = next.fetch_add(1, Ordering::SeqCst);
+        let Some(statement) = queries.get(i) else { break };
+        match run_statement(&mut *backend, canceller, options, i, statement, resp_tx) {
+            StatementEnd::Done | StatementEnd::Failed { connection_lost: false } => {}
+            // no cursor can stay open while other statements are running
+            StatementEnd::Capped { fetch, started, .. } => fetch.seal(started.elapsed(), resp_tx),
+            StatementEnd::Failed { connection_lost: true } => return true,
+        }
+    }
+    false
+}
+
+/// Run independent statements on the worker's connection plus up to
+/// `parallel_connections - 1` pooled ones, each taking the next statement as
+/// soon as it is free. Pooled connections are opened on first use and kept
+/// for the next parallel run. Returns true if the worker's own connection
+/// was lost.
+fn run_parallel<B: QueryBackend>(
+    backend: &mut B,
+    pool: &mut Vec<Box<dyn QueryBackend>>,
+    run_canceller: &RunCanceller,
+    options: &WorkerOptions,
+    queries: &[(String, String)],
+    resp_tx: &Sender<DbWorkerResponse>,
+) -> bool {
+    let wanted = options.parallel_connections.max(1).min(queries.len());
+    let idle = pool.len().min(wanted.saturating_sub(1));
+    let mut extra: Vec<(Box<dyn QueryBackend>, bool)> = pool.drain(..idle).map(|b| (b, true)).collect();
+    while extra.len() + 1 < wanted {
+        match backend.new_connection() {
+            Some(fresh) => extra.push((fresh, false)),
+            None => break,
+        }
+    }
+
+    *run_canceller.pooled.lock().unwrap() = extra.iter().map(|(b, _)| b.canceller()).collect();
+    run_canceller.cancelled.store(false, Ordering::SeqCst);
+    let next = AtomicUsize::new(0);
+
+    let (main_lost, healthy) = thread::scope(|scope| {
+        let handles: Vec<_> = extra
+            .into_iter()
+            .map(|(mut pooled, connected)| {
+                let resp_tx = resp_tx.clone();
+                let next = &next;
+                scope.spawn(move || {
+                    // new connections log in on their own thread; if that
+                    // fails the others take over its share of the statements
+                    if !connected && connect_backend(&mut pooled).is_err() {
+                        return None;
                     }
+                    let canceller = pooled.canceller();
+                    let lost = drain_queue(&mut pooled, &canceller, next, &run_canceller.cancelled, options, queries, &resp_tx);
+                    (!lost).then_some(pooled)
+                })
+            })
+            .collect();
+
+        let main_lost = drain_queue(backend, &run_canceller.main, &next, &run_canceller.cancelled, options, queries, resp_tx);
+        let healthy: Vec<_> = handles.into_iter().filter_map(|h| h.join().ok().flatten()).collect();
+        (main_lost, healthy)
+    });
+
+    run_canceller.pooled.lock().unwrap().clear();
+    pool.extend(healthy);
+    main_lost
+}
+
+/// Connect the worker's backend, retrying with exponential backoff. Statements
+/// sent while a retry is scheduled run once connected; the rest is refused.
+/// Returns false if the UI quit in the meantime.
+fn connect_with_retry<B: QueryBackend>(
+    backend: &mut B,
+    req_rx: &Receiver<DbWorkerRequest>,
+    resp_tx: &Sender<DbWorkerResponse>,
+    pending: &mut Option<DbWorkerRequest>,
+    mut reconnecting: bool,
+) -> bool {
+    // answer a run that can't happen so the UI (or batch) stops waiting for it;
+    // a serial run ends at its first error, parallel statements fail one by one
+    let refuse = |request: DbWorkerRequest, message: &str| {
+        let (queries, failing) = match &request {
+            DbWorkerRequest::RunQueries { queries, .. } => (queries, 1),
+            DbWorkerRequest::RunParallel(queries) => (queries, queries.len()),
+            _ => return,
+        };
+        for (i, (_, context)) in queries.iter().take(failing).enumerate() {
+            let _ = resp_tx.send(DbWorkerResponse::QueryStarted { query_idx: i, started: Instant::now(), query_context: context.clone() });
+            let _ = resp_tx.send(DbWorkerResponse::QueryError {
+                query_idx: i,
+                elapsed: Duration::ZERO,
+                message: format!("Not connected: {}", message),
+                query_id: None,
+            });
+        }
+        let _ = resp_tx.send(DbWorkerResponse::Idle);
+    };
+
+    let mut attempt = 0u32;
+    loop {
+        attempt += 1;
+        if reconnecting {
+            let _ = resp_tx.send(DbWorkerResponse::Reconnecting { attempt });
+        }
+        reconnecting = true;
+
+        let message = match connect_backend(backend) {
+            Ok(()) => {
+                let _ = resp_tx.send(DbWorkerResponse::Connected);
+                if let Some(context) = backend.session_context() {
+                    let _ = resp_tx.send(DbWorkerResponse::SessionContext(context));
                 }
-                return;
+                return true;
             }
+            Err(e) => e.message,
         };
+        let retry_in = (attempt <= MAX_RECONNECT_ATTEMPTS).then(|| RECONNECT_BACKOFF * 2u32.pow(attempt - 1));
+        let _ = resp_tx.send(DbWorkerResponse::Disconnected { message: message.clone(), retry_in });
 
+        if retry_in.is_none() {
+            if let Some(request) = pending.take() {
+                refuse(request, &message);
+            }
+        }
+        let deadline = retry_in.map(|d| Instant::now() + d);
         loop {
+            let request = match deadline {
+                Some(at) => req_rx.recv_timeout(at.saturating_duration_since(Instant::now())),
+                None => req_rx.recv().map_err(|_| mpsc::RecvTimeoutError::Disconnected),
+            };
+            match request {
+                Err(mpsc::RecvTimeoutError::Timeout) => break,
+                Ok(DbWorkerRequest::Reconnect) => {
+                    attempt = 0;
+                    break;
+                }
+                Ok(request @ (DbWorkerRequest::RunQueries { .. } | DbWorkerRequest::RunParallel(_))) if retry_in.is_some() => {
+                    *pending = Some(request);
+                }
+                Ok(request @ (DbWorkerRequest::RunQueries { .. } | DbWorkerRequest::RunParallel(_))) => refuse(request, &message),
+                Ok(DbWorkerRequest::SetTransactionMode { .. }) | Ok(DbWorkerRequest::Commit) | Ok(DbWorkerRequest::Rollback) => {
+                    let _ = resp_tx.send(DbWorkerResponse::TransactionError { message: "Not connected".to_string() });
+                }
+                Ok(DbWorkerRequest::FetchMore { .. }) => {
+                    let _ = resp_tx.send(DbWorkerResponse::Idle);
+                }
+                Ok(DbWorkerRequest::Cancel) => {}
+                Ok(DbWorkerRequest::Quit) | Err(mpsc::RecvTimeoutError::Disconnected) => return false,
+            }
+        }
+    }
+}
+
+/// Run `backend` on its own thread. With `connect_now` false the worker waits
+/// for a `Reconnect` (or a first run) before connecting, e.g. until the
+/// connection string's `${prompt:…}` values have been entered.
+pub fn start_db_worker<B: QueryBackend + 'static>(
+    mut backend: B,
+    options: WorkerOptions,
+    connect_now: bool,
+) -> (
+    Sender<DbWorkerRequest>,
+    Receiver<DbWorkerResponse>,
+    Arc<dyn QueryCanceller>
+) {
+    let (req_tx,  req_rx)  = mpsc::channel();
+    let (resp_tx, resp_rx) = mpsc::channel();
+
+    backend.set_value_options(options.values);
+
+    // cancels the statement that is *currently executing*
+    let thread_canceller = backend.canceller();
+    // ... and, for the UI, those of a parallel run too
+    let run_canceller = Arc::new(RunCanceller {
+        main: Arc::clone(&thread_canceller),
+        pooled: Mutex::new(Vec::new()),
+        cancelled: AtomicBool::new(false),
+    });
+    let canceller: Arc<dyn QueryCanceller> = run_canceller.clone();
+
+    thread::spawn(move || {
+        // a request that arrived while a capped cursor was parked (or while
+        // waiting to reconnect)
+        let mut pending: Option<DbWorkerRequest> = None;
+        // idle extra connections for parallel runs
+        let mut pool: Vec<Box<dyn QueryBackend>> = Vec::new();
+
+        if !connect_now {
             match req_rx.recv() {
-                Ok(DbWorkerRequest::RunQueries(queries_with_context)) => {
-                    for (i, (query, context)) in queries_with_context.iter().enumerate() {
-                        let started = Instant::now();
-                        let _ = resp_tx.send(DbWorkerResponse::QueryStarted { query_idx: i, started, query_context: context.clone() });
-                        // allocate a fresh statement
-                        let stmt = match Statement::with_parent(&conn) {
-                            Ok(s) => s,
-                            Err(e) => {
-                            let msg = format!("Statement Allocation Error: {:?}", e);
-                            let _ = resp_tx.send(DbWorkerResponse::QueryError {
-                                query_idx: i,
-                                elapsed: started.elapsed(),
-                                message: msg,
-                            });
-                            break;
-                            }
-                        };
+                Ok(DbWorkerRequest::Reconnect) => {}
+                Ok(DbWorkerRequest::Quit) | Err(_) => return,
+                Ok(request) => pending = Some(request),
+            }
+        }
+        if !connect_with_retry(&mut backend, &req_rx, &resp_tx, &mut pending, false) {
+            return;
+        }
 
-                        // 🔗 expose raw HSTMT so Ctrl-Backspace can call SQLCancel
-                        unsafe {
-                            *thread_stmt.lock().unwrap() =
-                                Some(SafeStmt(stmt.handle()));
+        loop {
+            let request = match pending.take() {
+                Some(req) => Ok(req),
+                None => req_rx.recv(),
+            };
+            match request {
+                Ok(DbWorkerRequest::RunQueries { queries: queries_with_context, continue_on_error }) => {
+                    run_canceller.cancelled.store(false, Ordering::SeqCst);
+                    // set when a statement fails because the connection is gone
+                    let mut connection_lost = false;
+                    // set when a parked cursor is closed by the next request;
+                    // the worker went idle when it parked
+                    let mut idle_reported = false;
+                    let last = queries_with_context.len().saturating_sub(1);
+                    for (i, statement) in queries_with_context.iter().enumerate() {
+                        // a cancel (or a profile switch) also drops the statements not started yet
+                        if run_canceller.cancelled.load(Ordering::SeqCst) {
+                            break;
                         }
-
-                        // Execute the SQL text.
-                        let exec_result = stmt.exec_direct(query);
-
-                        match exec_result {
-                            Ok(Data(mut stmt)) => {
-                                let cols = stmt.num_result_cols().unwrap();
-                                let col_names: Vec<String> = (1..=cols)
-                                    .map(|i| stmt.describe_col(i as u16).unwrap().name)
-                                    .collect();
-
-                                // Stream rows from ODBC using an iterator instead of RowIter struct!
-                                let tile_store = match TileRowStore::from_rows(
-                                    &col_names,
-                                    std::iter::from_fn(|| {
-                                        match stmt.fetch().unwrap() {
-                                            Some(mut cursor) => {
-                                                let mut row = Vec::with_capacity(col_names.len());
-                                                for idx in 0..col_names.len() {
-                                                let val: Option<String> = cursor.get_data(idx as u16 + 1).unwrap_or(None);
-                                                row.push(val.unwrap_or_else(|| NULL_SENTINEL.to_string()));
+                        match run_statement(&mut backend, &thread_canceller, &options, i, statement, &resp_tx) {
+                            StatementEnd::Done => {}
+                            StatementEnd::Failed { connection_lost: lost } => {
+                                connection_lost = lost;
+                                if !continue_on_error || lost || run_canceller.cancelled.load(Ordering::SeqCst) {
+                                    break;
+                                }
+                            }
+                            StatementEnd::Capped { fetch, started, .. } if i < last => {
+                                // Later statements need the connection; close this cursor
+                                fetch.seal(started.elapsed(), &resp_tx);
+                            }
+                            StatementEnd::Capped { mut fetch, started, limit, timeout } => {
+                                // ⏸ keep the cursor open until the user asks for more
+                                // rows or sends anything else
+                                let mut elapsed = started.elapsed();
+                                fetch.park(elapsed, &resp_tx);
+                                loop {
+                                    let _ = resp_tx.send(DbWorkerResponse::Idle);
+                                    match req_rx.recv() {
+                                        Ok(DbWorkerRequest::FetchMore { all }) => {
+                                            // time spent waiting on the user doesn't count
+                                            let resumed = Instant::now();
+                                            let total = |elapsed: Duration| elapsed + resumed.elapsed();
+                                            let watchdog = Watchdog::arm(timeout, &thread_canceller);
+                                            match fetch.fetch(if all { None } else { limit }, &resp_tx) {
+                                                FetchStop::Exhausted => {
+                                                    fetch.finish(total(elapsed), &resp_tx);
+                                                    break;
+                                                }
+                                                FetchStop::Capped => {
+                                                    elapsed = total(elapsed);
+                                                    fetch.park(elapsed, &resp_tx);
+                                                }
+                                                FetchStop::FetchError(_) if watchdog.fired() => {
+                                                    let _ = resp_tx.send(DbWorkerResponse::QueryTimedOut {
+                                                        query_idx: i,
+                                                        elapsed: total(elapsed),
+                                                        timeout: timeout.unwrap_or_default(),
+                                                    });
+                                                    break;
+                                                }
+                                                FetchStop::FetchError(e) if e.message.contains("HY008") => {
+                                                    // cancelled: keep what we have
+                                                    fetch.seal(total(elapsed), &resp_tx);
+                                                    break;
+                                                }
+                                                FetchStop::FetchError(e) => {
+                                                    connection_lost = e.is_connection_lost();
+                                                    let _ = resp_tx.send(DbWorkerResponse::QueryError {
+                                                        query_idx: i,
+                                                        elapsed: total(elapsed),
+                                                        message: format!("Fetch Error: {}", e),
+                                                        query_id: fetch.query_id.clone(),
+                                                    });
+                                                    break;
+                                                }
+                                                FetchStop::StoreError(e) => {
+                                                    let _ = resp_tx.send(DbWorkerResponse::QueryError {
+                                                        query_idx: i,
+                                                        elapsed: total(elapsed),
+                                                        message: format!("TileRowStore error: {e:?}"),
+                                                        query_id: fetch.query_id.clone(),
+                                                    });
+                                                    break;
                                                 }
-                                                Some(row)
                                             }
-                                            None => None,
                                         }
-                                    })
-                                ) {
-                                    Ok(ts) => ts,
-                                    Err(e) => {
-                                        let msg = format!("TileRowStore error: {e:?}");
-                                        let _ = resp_tx.send(DbWorkerResponse::QueryError {
-                                            query_idx: i,
-                                            elapsed: started.elapsed(),
-                                            message: msg,
-                                        });
-                                        continue;
-                                    }
-                                };
-
-                                let _ = resp_tx.send(DbWorkerResponse::QueryFinished {
-                                    query_idx: i,
-                                    elapsed: started.elapsed(),
-                                    result: ResultsContent::Table {
-                                        headers: col_names,
-                                        tile_store,
-                                    },
-                                });
-                            }
-                            Ok(ResultSetState::NoData(statement)) => {
-                                let msg = {
-                                    if let Ok(cnt) = statement.affected_row_count() {
-                                        if cnt > 0 {
-                                            format!("Statement affected {} row{}", cnt, if cnt == 1 { "" } else { "s" })
-                                        } else if cnt == 0 {
-                                            "Statement executed successfully (no rows affected).".to_string()
-                                        } else {
-                                            "Statement executed successfully.".to_string()
+                                        Ok(other) => {
+                                            // the live view in the tab stops following
+                                            // the file once it has the sealed store
+                                            fetch.seal_parked(&resp_tx);
+                                            idle_reported = true;
+                                            pending = Some(other);
+                                            break;
                                         }
-                                    } else {
-                                        "Statement executed successfully.".to_string()
+                                        Err(_) => return,
                                     }
-                                };
-                                let _ = resp_tx.send(DbWorkerResponse::QueryFinished {
-                                    query_idx: i,
-                                    elapsed: started.elapsed(),
-                                    result: ResultsContent::Info { message: msg },
-                                });
+                                }
                             }
-                            Err(e) => {
-                                // Could be user-cancelled (HY008) or some other error
-                                let msg = format!("Execution Error: {:?}", e);
-                                let _ = resp_tx.send(DbWorkerResponse::QueryError {
-                                    query_idx: i,
-                                    elapsed: started.elapsed(),
-                                    message: msg,
-                                });
-                                break;
+                        }
+                        // pooled connections of parallel runs have sessions of
+                        // their own, so only serial runs are checked
+                        if Workspace::changes_session_context(&statement.0) {
+                            if let Some(context) = backend.session_context() {
+                                let _ = resp_tx.send(DbWorkerResponse::SessionContext(context));
                             }
                         }
-                        // clear handle – we're done with it (success or error)
-                        *thread_stmt.lock().unwrap() = None;
                     }
+                    if !idle_reported {
+                        let _ = resp_tx.send(DbWorkerResponse::Idle);
+                    }
+
+                    if connection_lost && !connect_with_retry(&mut backend, &req_rx, &resp_tx, &mut pending, true) {
+                        break;
+                    }
+                }
+                Ok(DbWorkerRequest::RunParallel(queries_with_context)) => {
+                    let connection_lost = run_parallel(&mut backend, &mut pool, &run_canceller, &options, &queries_with_context, &resp_tx);
+                    let _ = resp_tx.send(DbWorkerResponse::Idle);
+                    if connection_lost && !connect_with_retry(&mut backend, &req_rx, &resp_tx, &mut pending, true) {
+                        break;
+                    }
+                }
+                Ok(DbWorkerRequest::Reconnect) => {
+                    pool.clear();  // the next parallel run opens fresh ones
+                    if !connect_with_retry(&mut backend, &req_rx, &resp_tx, &mut pending, true) {
+                        break;
+                    }
+                }
+                Ok(DbWorkerRequest::SetTransactionMode { manual }) => {
+                    let _ = resp_tx.send(match backend.set_manual_transactions(manual) {
+                        Ok(()) => DbWorkerResponse::TransactionModeChanged { manual },
+                        Err(e) => DbWorkerResponse::TransactionError { message: e.message },
+                    });
+                }
+                Ok(DbWorkerRequest::Commit) => {
+                    let _ = resp_tx.send(match backend.commit() {
+                        Ok(()) => DbWorkerResponse::TransactionEnded { committed: true },
+                        Err(e) => DbWorkerResponse::TransactionError { message: e.message },
+                    });
+                }
+                Ok(DbWorkerRequest::Rollback) => {
+                    let _ = resp_tx.send(match backend.rollback() {
+                        Ok(()) => DbWorkerResponse::TransactionEnded { committed: false },
+                        Err(e) => DbWorkerResponse::TransactionError { message: e.message },
+                    });
                 }
                 Ok(DbWorkerRequest::Cancel) => {
                     // user hit Ctrl + Backspace
-                    if let Some(h) = *thread_stmt.lock().unwrap() {
-                        unsafe { let _ = SQLCancel(h.0); };
-                    }
+                    thread_canceller.cancel();
+                }
+                Ok(DbWorkerRequest::FetchMore { .. }) => {
+                    // no parked cursor (it was closed by a newer request)
+                    let _ = resp_tx.send(DbWorkerResponse::Idle);
                 }
                 Ok(DbWorkerRequest::Quit) | Err(_) => {
                     break;
                 }
             }
         }
-    });
+    });
+
+    (req_tx, resp_rx, canceller)
+}
+
+fn hit(r: Rect, x: u16, y: u16) -> bool {
+        x >= r.x && x < r.x + r.width && y >= r.y && y < r.y + r.height
+    }
+
+impl Workspace {
+    pub fn new<B: QueryBackend + 'static>(backend: B, worker_options: WorkerOptions) -> Result<Self> {
+        let wrap_script_blocks = backend.wraps_script_blocks();
+        let backslash_escapes = backend.backslash_escapes();
+        // connects once `set_connection_string` has the prompted values
+        let (db_req_tx, db_resp_rx, canceller) = start_db_worker(backend, worker_options.clone(), false);
+        let mut editor = Editor::new();
+        let db_tree = DbTree::new();
+        
+        // Initialize schema cache for autocomplete
+        if let Some(cache) = db_tree.cache.clone() {
+            editor.schema_cache = Some(cache);
+        }
+        
+        Ok(Self {
+            editor,
+            results: Results::new(),
+            error: None,
+            running: false,
+            batch_generator: None,
+            save_as_dialog: None,
+            profile_picker: None,
+            bind_dialog: None,
+            secret_prompt: None,
+            cell_inspector: None,
+            connection_string: String::new(),
+            bind_values: HashMap::new(),
+            history_browser: None,
+            history: QueryHistory::open(&QueryHistory::default_path()).ok(),
+            run_statements: Vec::new(),
+            run_sources: Vec::new(),
+            history_ids: Vec::new(),
+            run_started: None,
+            run_duration: None,
+            running_query_idx: None,
+            parallel_remaining: None,
+            worker_busy: false,
+            run_queue: VecDeque::new(),
+            last_editor_area: None,
+            last_results_area: None,
+            focus: Focus::Editor,
+            last_esc_down: false,
+            status_message: None,
+            status_message_time: None,
+            db_req_tx,
+            db_resp_rx,
+            old_resp_rxs: Vec::new(),
+            canceller,
+            wrap_script_blocks,
+            backslash_escapes,
+            worker_options,
+            profiles: BTreeMap::new(),
+            active_profile: None,
+            total_queries: 0,
+            split_offset: 0,
+            min_split_offset:  0,
+            max_split_offset:  0,
+            results_hidden: false,
+            editor_hidden: false,
+            show_help: false,
+            drag_source: None,
+            show_exit_dialog: false,
+            exit_requested: false,
+            exit_after_save: false,
+            file_path: None,
+            original_content: String::new(),
+            connection: ConnectionState::Connecting,
+            transaction: None,
+            session: None,
+            continue_on_error: false,
+            run_continues: false,
+            run_summary: None,
+            txn_prompt: None,
+            after_transaction: None,
+            exit_ready: false,
+            db_tree, 
+            frame_counter: 0,
+        })
+    }
+
+    /// Record the configured profiles and which one the current worker uses
+    pub fn set_profiles(&mut self, profiles: BTreeMap<String, Profile>, active: Option<String>) {
+        self.profiles = profiles;
+        self.active_profile = active;
+        self.db_tree.set_profile(self.active_profile.as_deref());
+        self.editor.schema_cache = self.db_tree.cache.clone();
+    }
+
+    /// The connection string the worker uses. The worker connects now, or
+    /// once its `${prompt:…}` values have been entered.
+    pub fn set_connection_string(&mut self, conn_str: String) {
+        self.connection_string = conn_str;
+        if !self.prompt_for_secrets() {
+            let _ = self.db_req_tx.send(DbWorkerRequest::Reconnect);
+        }
+    }
+
+    /// Open the masked prompt for missing `${prompt:…}` values. Returns false
+    /// if there is nothing to ask for.
+    fn prompt_for_secrets(&mut self) -> bool {
+        let missing = crate::secrets::missing_prompts(&self.connection_string);
+        if missing.is_empty() {
+            return false;
+        }
+        self.secret_prompt = Some(SecretPromptDialog::new(missing));
+        true
+    }
+
+    fn has_open_transaction(&self) -> bool {
+        self.transaction.is_some_and(|n| n > 0)
+    }
+
+    /// Stop a run at its first failure or keep going (F12)
+    pub fn toggle_continue_on_error(&mut self) {
+        self.continue_on_error = !self.continue_on_error;
+        self.status_message = Some(if self.continue_on_error {
+            "Continue on error: a failed statement no longer stops the run".to_string()
+        } else {
+            "Stop on error: a failed statement skips the rest of the run".to_string()
+        });
+        self.status_message_time = Some(Instant::now());
+    }
+
+    /// Switch between autocommit and manual commit (F6)
+    pub fn toggle_transaction_mode(&mut self) {
+        let message = if self.running {
+            "Wait for the running query to finish first"
+        } else if self.has_open_transaction() {
+            "Commit (F7) or roll back (F8) the open transaction first"
+        } else {
+            let manual = self.transaction.is_none();
+            let _ = self.db_req_tx.send(DbWorkerRequest::SetTransactionMode { manual });
+            return;
+        };
+        self.status_message = Some(message.to_string());
+        self.status_message_time = Some(Instant::now());
+    }
+
+    pub fn commit_transaction(&mut self) {
+        self.end_transaction(DbWorkerRequest::Commit);
+    }
+
+    pub fn rollback_transaction(&mut self) {
+        self.end_transaction(DbWorkerRequest::Rollback);
+    }
+
+    fn end_transaction(&mut self, request: DbWorkerRequest) {
+        let message = if self.transaction.is_none() {
+            "Autocommit is on – press F6 for manual commit mode"
+        } else if self.running {
+            "Wait for the running query to finish first"
+        } else {
+            let _ = self.db_req_tx.send(request);
+            return;
+        };
+        self.after_transaction = None;
+        self.status_message = Some(message.to_string());
+        self.status_message_time = Some(Instant::now());
+    }
+
+    /// Ask the DB worker to drop its connection and connect again
+    pub fn reconnect(&mut self) {
+        if self.running {
+            self.status_message = Some("Cancel the running query before reconnecting".to_string());
+            self.status_message_time = Some(Instant::now());
+            return;
+        }
+        if self.prompt_for_secrets() {
+            return;  // reconnects once the values are in
+        }
+        let _ = self.db_req_tx.send(DbWorkerRequest::Reconnect);
+        self.status_message = Some("Reconnecting…".to_string());
+        self.status_message_time = Some(Instant::now());
+    }
+
+    /// Tear down the DB worker and reconnect with another profile
+    pub fn switch_profile(&mut self, name: &str) {
+        let Some(profile) = self.profiles.get(name).cloned() else {
+            self.status_message = Some(format!("Unknown profile '{}'", name));
+            self.status_message_time = Some(Instant::now());
+            return;
+        };
+
+        // Stop whatever the old connection is doing, then let its thread exit
+        if self.running {
+            self.canceller.cancel();
+            self.running = false;
+            self.running_query_idx = None;
+            self.parallel_remaining = None;
+            self.run_duration = self.run_started.map(|s| s.elapsed());
+        }
+        let _ = self.db_req_tx.send(DbWorkerRequest::Quit);
+
+        let conn_str = profile.effective_connection_string();
+        let backend = backend_for(&conn_str);
+        self.wrap_script_blocks = backend.wraps_script_blocks();
+        self.backslash_escapes = backend.backslash_escapes();
+        let (db_req_tx, db_resp_rx, canceller) = start_db_worker(backend, self.worker_options.clone(), false);
+        self.db_req_tx = db_req_tx;
+        self.old_resp_rxs.push(std::mem::replace(&mut self.db_resp_rx, db_resp_rx));
+        self.canceller = canceller;
+        self.connection = ConnectionState::Connecting;
+        self.transaction = None;   // the new connection starts in autocommit
+        self.worker_busy = false;
+        if !self.run_queue.is_empty() {
+            self.run_queue.clear();
+            self.sync_queued_tabs();
+        }
+        for tab in &mut self.results.tabs {
+            tab.query_idx = None;  // the old worker's responses are gone
+        }
 
-    (req_tx, resp_rx, current_stmt)
-}
+        self.active_profile = Some(name.to_string());
+        self.db_tree.set_profile(Some(name));
+        self.editor.schema_cache = self.db_tree.cache.clone();
+        self.set_session_context(None);
+        self.set_connection_string(conn_str);
 
-fn hit(r: Rect, x: u16, y: u16) -> bool {
-        x >= r.x && x < r.x + r.width && y >= r.y && y < r.y + r.height
+        self.status_message = Some(format!("Switched to profile '{}' – connecting…", name));
+        self.status_message_time = Some(Instant::now());
     }
 
-impl Workspace {
-    pub fn new(conn_str: String) -> Result<Self> {
-        let (db_req_tx, db_resp_rx, current_stmt) = start_db_worker(conn_str.clone());
-        let mut editor = Editor::new();
-        let db_tree = DbTree::new();
-        
-        // Initialize schema cache for autocomplete
-        if let Some(cache) = db_tree.cache.clone() {
-            editor.schema_cache = Some(cache);
+    /// Hand the session context to everything that resolves unqualified names
+    fn set_session_context(&mut self, context: Option<SessionContext>) {
+        self.db_tree.set_session_context(context.clone());
+        self.editor.session_context = context.clone();
+        self.session = context;
+        self.update_title();
+    }
+
+    /// Continue the current tab's result set past the row cap
+    fn fetch_more(&mut self, all: bool) {
+        let idx = self.results.tab_idx;
+        let resumable = self.results.tabs.get(idx)
+            .map_or(false, |tab| tab.truncated == Some(Truncation::Resumable));
+        if !resumable || self.worker_busy {
+            self.status_message = Some("No more rows to fetch for this tab".to_string());
+            self.status_message_time = Some(Instant::now());
+            return;
         }
-        
-        Ok(Self {
-            editor,
-            results: Results::new(),
-            error: None,
-            running: false,
-            batch_generator: None,
-            save_as_dialog: None,
-            run_started: None,
-            run_duration: None,
-            running_query_idx: None,
-            last_editor_area: None,
-            last_results_area: None,
-            focus: Focus::Editor,
-            last_esc_down: false,
-            status_message: None,
-            status_message_time: None,
-            db_req_tx,
-            db_resp_rx,
-            current_stmt,
-            total_queries: 0,
-            split_offset: 0,
-            min_split_offset:  0,
-            max_split_offset:  0,
-            results_hidden: false,
-            editor_hidden: false,
-            show_help: false,
-            drag_source: None,
-            show_exit_dialog: false,
-            exit_requested: false,
-            exit_after_save: false,
-            file_path: None,
-            original_content: String::new(),
-            connected: false,
-            db_tree, 
-            frame_counter: 0,
-        })
+
+        let _ = self.db_req_tx.send(DbWorkerRequest::FetchMore { all });
+        self.worker_busy = true;
+        let tab = &mut self.results.tabs[idx];
+        tab.truncated = None;
+        tab.running = true;
+        tab.run_started = Some(Instant::now());
+        self.running = true;
+        self.running_query_idx = Some(idx);
+        self.run_started = tab.run_started;
     }
 
     /// Update the console title based on current file and dirty state
     fn update_title(&self) {
+        let title = if let Some(path) = &self.file_path {
+            let fname = path.file_name()
+                .unwrap_or_default()
+                .to_string_lossy();
+            if self.editor.dirty {
+                format!("{}*", fname)
+            } else {
+                format!("{}", fname)
+            }
+        } else {
+            if self.editor.dirty {
+                "[No Name]*".to_string()
+            } else {
+                "[No Name]".to_string()
+            }
+        };
+        let title = match &self.session {
+            Some(context) => format!("{} – {}", title, context.label()),
+            None => title,
+        };
+
+        #[cfg(not(windows))]
+        {
+            let _ = crossterm::execute!(std::io::stdout(), crossterm::terminal::SetTitle(&title));
+        }
         #[cfg(windows)]
         {
-            let title = if let Some(path) = &self.file_path {
-                let fname = path.file_name()
-                    .unwrap_or_default()
-                    .to_string_lossy();
-                if self.editor.dirty {
-                    format!("{}*", fname)
-                } else {
-                    format!("{}", fname)
-                }
-            } else {
-                if self.editor.dirty {
-                    "[No Name]*".to_string()
-                } else {
-                    "[No Name]".to_string()
-                }
-            };
-            
             // Call Windows API to set title
             use std::ffi::OsStr;
             use std::os::windows::prelude::*;
@@ -887,6 +2184,49 @@ impl Workspace {
         f.render_widget(paragraph, inner);
     }
 
+    fn render_txn_prompt<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        use tui::widgets::*;
+        use tui::text::*;
+
+        let action = match &self.txn_prompt {
+            Some(AfterTransaction::Exit) => "exiting".to_string(),
+            Some(AfterTransaction::SwitchProfile(name)) => format!("switching to '{}'", name),
+            None => return,
+        };
+
+        let dialog_width = 60.min(area.width);
+        let dialog_height = 7.min(area.height);
+
+        let dialog_area = Rect {
+            x: area.width.saturating_sub(dialog_width) / 2,
+            y: area.height.saturating_sub(dialog_height) / 2,
+            width: dialog_width,
+            height: dialog_height,
+        };
+
+        f.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .title(" Open Transaction ")
+            .borders(Borders::ALL)
+            .border_style(STYLE::help_border());
+
+        let inner = block.inner(dialog_area);
+        f.render_widget(block, dialog_area);
+
+        let text = vec![
+            Spans::from(""),
+            Spans::from(format!("  {} uncommitted statement(s) in this session.", self.transaction.unwrap_or(0))),
+            Spans::from(format!("  End the transaction before {}?", action)),
+            Spans::from(""),
+            Spans::from("  C: Commit  R: Roll back  Esc: Cancel"),
+        ];
+
+        let paragraph = Paragraph::new(text)
+            .style(STYLE::plain());
+        f.render_widget(paragraph, inner);
+    }
+
     fn render_help<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
         use tui::widgets::*;
 
@@ -902,9 +2242,19 @@ impl Workspace {
             "      Esc                  Cycle focus between visible panes",
             "      Tab                  Switch between find/replace fields",
             "      [ ]                  Previous/next result tab",
+            "      n / N                Fetch next chunk / all rows (capped results)",
+            "      x / X                Close result tab / clear finished tabs",
+            "      { / }                Previous/next failed result tab",
+            "      < / >                Move a queued run earlier / later",
+            "      e                    Jump to the error position (error tab)",
+            "      i                    Copy the tab's query ID",
+            "      v                    Show the full value of the cell under the cursor",
+            "      w                    Save the result tab as a .frostres file",
+            "      Ctrl + O             Open a saved .frostres result",
             "",
             "  📝 Editing & Execution", 
-            "      Ctrl + Enter         Execute selection or statement at cursor",
+            "      Ctrl + Enter         Execute selection or statement at cursor (queues if busy)",
+            "      F4                   Execute selected statements in parallel",
             "      Ctrl + Backspace     Cancel running query",
             "      Ctrl + A             Select all",
             "      Ctrl + C             Copy selection",
@@ -929,6 +2279,12 @@ impl Workspace {
             "  💾 Data Export & Tools",
             "      F9                   Export results to CSV",
             "      F10                  Generate batch script",
+            "      F2                   Switch connection profile",
+            "      F3                   Query history (Enter: insert, Ctrl+Enter: run)",
+            "      F5                   Reconnect to the database",
+            "      F6                   Toggle manual commit mode",
+            "      F7 / F8              Commit / roll back the open transaction",
+            "      F12                  Toggle continue on error for multi-statement runs",
             "      Ctrl + R             Refresh current database object (Navigator)",
             "      Ctrl + Shift + R     Full schema refresh (Navigator)",
             "      Ctrl + U             Select role filter (Navigator)",
@@ -1090,87 +2446,393 @@ impl Workspace {
                 lines.push(Spans::from(Span::styled(msg, Style::default().fg(Color::Yellow))));
                 lines.push(Spans::from(Span::raw("")));
             }
-            
-            // Instructions
-            lines.push(Spans::from(Span::raw("Space: Toggle checkboxes")));
-            lines.push(Spans::from(Span::raw("Enter: Generate script")));
-            lines.push(Spans::from(Span::raw("Esc/F10: Cancel")));
-            
-            let block = Block::default()
-                .title(" Generate Batch Script (F10) ")
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan));
-                
-            let paragraph = Paragraph::new(lines)
-                .block(block)
-                .wrap(Wrap { trim: true });
-                
-            f.render_widget(paragraph, dialog_area);
+            
+            // Instructions
+            lines.push(Spans::from(Span::raw("Space: Toggle checkboxes")));
+            lines.push(Spans::from(Span::raw("Enter: Generate script")));
+            lines.push(Spans::from(Span::raw("Esc/F10: Cancel")));
+            
+            let block = Block::default()
+                .title(" Generate Batch Script (F10) ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan));
+                
+            let paragraph = Paragraph::new(lines)
+                .block(block)
+                .wrap(Wrap { trim: true });
+                
+            f.render_widget(paragraph, dialog_area);
+        }
+    }
+
+    fn render_save_as_dialog<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        use tui::widgets::*;
+        use tui::text::*;
+        use tui::style::{Style, Modifier, Color};
+        
+        if let Some(dialog) = &self.save_as_dialog {
+            let dialog_width = 60.min(area.width - 4);
+            let dialog_height = 8.min(area.height - 4);
+            
+            let dialog_area = Rect {
+                x: (area.width - dialog_width) / 2,
+                y: (area.height - dialog_height) / 2,
+                width: dialog_width,
+                height: dialog_height,
+            };
+            
+            f.render_widget(Clear, dialog_area);
+            
+            let (title, action) = match dialog.purpose {
+                PathPurpose::SaveSql => (" Save As ", "Save"),
+                PathPurpose::SaveResult => (" Save Result ", "Save"),
+                PathPurpose::OpenResult => (" Open Result ", "Open"),
+            };
+            let block = Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan));
+            
+            let inner = block.inner(dialog_area);
+            f.render_widget(block, dialog_area);
+            
+            let mut lines = vec![
+                Spans::from("Enter filename:"),
+                Spans::from(""),
+            ];
+            
+            // Show the file path with cursor
+            let mut path_spans = vec![];
+            for (i, ch) in dialog.file_path.chars().enumerate() {
+                if i == dialog.cursor_pos {
+                    path_spans.push(Span::styled(
+                        ch.to_string(),
+                        Style::default().add_modifier(Modifier::REVERSED)
+                    ));
+                } else {
+                    path_spans.push(Span::raw(ch.to_string()));
+                }
+            }
+            
+            // Add cursor at end if needed
+            if dialog.cursor_pos == dialog.file_path.len() {
+                path_spans.push(Span::styled(
+                    " ",
+                    Style::default().add_modifier(Modifier::REVERSED)
+                ));
+            }
+            
+            lines.push(Spans::from(path_spans));
+            lines.push(Spans::from(""));
+            
+            // Show message if any
+            if let Some(msg) = &dialog.message {
+                lines.push(Spans::from(Span::styled(msg, Style::default().fg(Color::Yellow))));
+            } else {
+                lines.push(Spans::from(format!("Enter: {}  Esc: Cancel", action)));
+            }
+            
+            let paragraph = Paragraph::new(lines);
+            f.render_widget(paragraph, inner);
+        }
+    }
+
+    fn render_profile_picker<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        use tui::widgets::*;
+        use tui::text::*;
+        use tui::style::{Style, Modifier, Color};
+
+        if let Some(dialog) = &self.profile_picker {
+            let dialog_width = 50.min(area.width.saturating_sub(4));
+            let dialog_height = (dialog.names.len() as u16 + 4).min(area.height.saturating_sub(4));
+
+            let dialog_area = Rect {
+                x: (area.width - dialog_width) / 2,
+                y: (area.height - dialog_height) / 2,
+                width: dialog_width,
+                height: dialog_height,
+            };
+
+            f.render_widget(Clear, dialog_area);
+
+            let block = Block::default()
+                .title(" Connection Profiles ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan));
+
+            let inner = block.inner(dialog_area);
+            f.render_widget(block, dialog_area);
+
+            let mut lines = Vec::new();
+            for (i, name) in dialog.names.iter().enumerate() {
+                let marker = if self.active_profile.as_deref() == Some(name.as_str()) { "● " } else { "  " };
+                let swatch = match self.profiles.get(name).and_then(|p| p.accent) {
+                    Some(accent) => Span::styled("  ", Style::default().bg(crate::palette::rgb(accent))),
+                    None => Span::raw("  "),
+                };
+                let label = format!("{}{}", marker, name);
+                let label = if i == dialog.selected {
+                    Span::styled(label, Style::default().add_modifier(Modifier::REVERSED))
+                } else {
+                    Span::raw(label)
+                };
+                lines.push(Spans::from(vec![swatch, Span::raw(" "), label]));
+            }
+            lines.push(Spans::from(""));
+            lines.push(Spans::from("Enter: Connect  Esc: Cancel"));
+
+            let paragraph = Paragraph::new(lines);
+            f.render_widget(paragraph, inner);
+        }
+    }
+
+    fn render_history_browser<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        use tui::widgets::*;
+        use tui::text::*;
+        use tui::style::{Style, Modifier, Color};
+
+        if let Some(browser) = &self.history_browser {
+            let dialog_area = Rect {
+                x: 4.min(area.width / 8),
+                y: 2.min(area.height / 8),
+                width: area.width - 2 * 4.min(area.width / 8),
+                height: area.height - 2 * 2.min(area.height / 8),
+            };
+
+            f.render_widget(Clear, dialog_area);
+
+            let block = Block::default()
+                .title(format!(" Query History ({}/{}) ", browser.matches.len(), browser.entries.len()))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan));
+
+            let inner = block.inner(dialog_area);
+            f.render_widget(block, dialog_area);
+
+            // search line, list, preview (up to 6 lines), footer
+            let preview_height = 6u16;
+            let list_height = inner.height.saturating_sub(preview_height + 4) as usize;
+            let offset = (browser.selected + 1).saturating_sub(list_height);
+
+            let mut lines = vec![Spans::from(vec![
+                Span::raw("Search: "),
+                Span::raw(browser.query.clone()),
+                Span::styled(" ", Style::default().add_modifier(Modifier::REVERSED)),
+            ])];
+            lines.push(Spans::from(""));
+
+            for (row, &i) in browser.matches.iter().enumerate().skip(offset).take(list_height) {
+                let e = &browser.entries[i];
+                let status = match &e.error {
+                    Some(_) => Span::styled("✗ ", Style::default().fg(Color::Red)),
+                    None => Span::styled("✓ ", Style::default().fg(Color::Green)),
+                };
+                let elapsed = e.elapsed_ms.map(|ms| format!("{:.2}s", ms as f64 / 1000.0)).unwrap_or_default();
+                let rows = e.row_count.map(|n| format!("{} rows", n)).unwrap_or_default();
+                let first_line = e.sql.lines().map(str::trim).find(|l| !l.is_empty()).unwrap_or("");
+                let text = format!(
+                    "{}  {:>9}  {:>12}  {:<12}  {}",
+                    e.executed_at,
+                    elapsed,
+                    rows,
+                    e.profile.as_deref().unwrap_or("-"),
+                    first_line,
+                );
+                let text = if row == browser.selected {
+                    Span::styled(text, Style::default().add_modifier(Modifier::REVERSED))
+                } else {
+                    Span::raw(text)
+                };
+                lines.push(Spans::from(vec![status, text]));
+            }
+            while lines.len() < list_height + 2 {
+                lines.push(Spans::from(""));
+            }
+
+            lines.push(Spans::from(Span::styled(
+                "─".repeat(inner.width as usize),
+                Style::default().fg(Color::DarkGray),
+            )));
+            if let Some(e) = browser.selected_entry() {
+                let mut preview: Vec<Spans> = e.sql
+                    .lines()
+                    .take(preview_height as usize - 1)
+                    .map(|l| Spans::from(l.to_string()))
+                    .collect();
+                let detail = match (&e.error, &e.role) {
+                    (Some(err), _) => Span::styled(
+                        err.lines().next().unwrap_or("").to_string(),
+                        Style::default().fg(Color::Red),
+                    ),
+                    (None, Some(role)) => Span::styled(format!("Role: {}", role), Style::default().fg(Color::DarkGray)),
+                    (None, None) => Span::raw(""),
+                };
+                preview.push(Spans::from(detail));
+                lines.extend(preview);
+            }
+            while lines.len() < list_height + 3 + preview_height as usize {
+                lines.push(Spans::from(""));
+            }
+            lines.push(Spans::from("Enter: Insert  Ctrl+Enter: Run  Esc: Close"));
+
+            let paragraph = Paragraph::new(lines);
+            f.render_widget(paragraph, inner);
+        }
+    }
+
+    fn render_cell_inspector<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        use tui::widgets::*;
+        use tui::text::*;
+        use tui::style::{Style, Color};
+
+        let Some(inspector) = &self.cell_inspector else { return };
+        let dialog_area = CellInspector::area(area);
+        f.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .title(format!(
+                " {} – {} ",
+                inspector.title,
+                crate::cell_value::format_size(inspector.text.len() as u64)
+            ))
+            .borders(Borders::ALL)
+            .border_style(STYLE::help_border());
+
+        let inner = block.inner(dialog_area);
+        f.render_widget(block, dialog_area);
+
+        let body_height = inner.height.saturating_sub(1) as usize;
+        let mut lines: Vec<Spans> = inspector.lines
+            .iter()
+            .skip(inspector.scroll)
+            .take(body_height)
+            .map(|line| Spans::from(line.as_str()))
+            .collect();
+        lines.resize(body_height, Spans::from(""));
+        let shown = (inspector.scroll + body_height).min(inspector.lines.len());
+        lines.push(Spans::from(Span::styled(
+            format!(
+                "Lines {}-{} of {}   ↑↓ PgUp/PgDn: Scroll  c: Copy  Esc: Close",
+                (inspector.scroll + 1).min(shown),
+                shown,
+                inspector.lines.len()
+            ),
+            Style::default().fg(Color::DarkGray),
+        )));
+
+        f.render_widget(Paragraph::new(lines), inner);
+    }
+
+    fn render_secret_prompt<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        use tui::widgets::*;
+        use tui::text::*;
+        use tui::style::{Style, Modifier, Color};
+
+        let Some(dialog) = &self.secret_prompt else { return };
+        let dialog_width = 60.min(area.width.saturating_sub(4));
+        let dialog_height = (dialog.labels.len() as u16 + 5).min(area.height.saturating_sub(4));
+
+        let dialog_area = Rect {
+            x: (area.width - dialog_width) / 2,
+            y: (area.height - dialog_height) / 2,
+            width: dialog_width,
+            height: dialog_height,
+        };
+
+        f.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .title(" Connection ")
+            .borders(Borders::ALL)
+            .border_style(STYLE::help_border());
+
+        let inner = block.inner(dialog_area);
+        f.render_widget(block, dialog_area);
+
+        let label_width = dialog.labels.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let mut lines = Vec::new();
+        for (i, (label, value)) in dialog.labels.iter().zip(&dialog.values).enumerate() {
+            // never draw the secret itself
+            let mut spans = vec![
+                Span::raw(format!("{:<w$}  ", label, w = label_width)),
+                Span::raw("•".repeat(value.chars().count())),
+            ];
+            if i == dialog.selected {
+                spans.push(Span::styled(" ", Style::default().add_modifier(Modifier::REVERSED)));
+            }
+            lines.push(Spans::from(spans));
         }
+        lines.push(Spans::from(""));
+        lines.push(Spans::from(Span::styled(
+            "Kept in memory for this session only",
+            Style::default().fg(Color::DarkGray),
+        )));
+        lines.push(Spans::from("Enter: Connect  Tab: Next  Esc: Cancel"));
+
+        let paragraph = Paragraph::new(lines);
+        f.render_widget(paragraph, inner);
     }
 
-    fn render_save_as_dialog<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+    fn render_bind_dialog<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
         use tui::widgets::*;
         use tui::text::*;
         use tui::style::{Style, Modifier, Color};
-        
-        if let Some(dialog) = &self.save_as_dialog {
-            let dialog_width = 60.min(area.width - 4);
-            let dialog_height = 8.min(area.height - 4);
-            
+
+        if let Some(dialog) = &self.bind_dialog {
+            let dialog_width = 70.min(area.width.saturating_sub(4));
+            let dialog_height = (dialog.names.len() as u16 + 6).min(area.height.saturating_sub(4));
+
             let dialog_area = Rect {
                 x: (area.width - dialog_width) / 2,
                 y: (area.height - dialog_height) / 2,
                 width: dialog_width,
                 height: dialog_height,
             };
-            
+
             f.render_widget(Clear, dialog_area);
-            
+
             let block = Block::default()
-                .title(" Save As ")
+                .title(" Bind Variables ")
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan));
-            
+
             let inner = block.inner(dialog_area);
             f.render_widget(block, dialog_area);
-            
-            let mut lines = vec![
-                Spans::from("Enter filename:"),
-                Spans::from(""),
-            ];
-            
-            // Show the file path with cursor
-            let mut path_spans = vec![];
-            for (i, ch) in dialog.file_path.chars().enumerate() {
-                if i == dialog.cursor_pos {
-                    path_spans.push(Span::styled(
-                        ch.to_string(),
-                        Style::default().add_modifier(Modifier::REVERSED)
-                    ));
+
+            let name_width = dialog.names.iter().map(|n| n.chars().count()).max().unwrap_or(0);
+            let mut lines = Vec::new();
+            for (i, (name, value)) in dialog.names.iter().zip(&dialog.values).enumerate() {
+                let mut spans = vec![Span::raw(format!("{:<w$}  ", name, w = name_width))];
+                if i == dialog.selected {
+                    for (j, ch) in value.text.chars().enumerate() {
+                        if j == dialog.cursor_pos {
+                            spans.push(Span::styled(ch.to_string(), Style::default().add_modifier(Modifier::REVERSED)));
+                        } else {
+                            spans.push(Span::raw(ch.to_string()));
+                        }
+                    }
+                    if dialog.cursor_pos == value.text.chars().count() {
+                        spans.push(Span::styled(" ", Style::default().add_modifier(Modifier::REVERSED)));
+                    }
                 } else {
-                    path_spans.push(Span::raw(ch.to_string()));
+                    spans.push(Span::raw(value.text.clone()));
                 }
+                let hint = match value.kind {
+                    BindKind::Auto => format!("  ({})", value.hint()),
+                    kind => format!("  [{}]", kind.label()),
+                };
+                spans.push(Span::styled(hint, Style::default().fg(Color::DarkGray)));
+                lines.push(Spans::from(spans));
             }
-            
-            // Add cursor at end if needed
-            if dialog.cursor_pos == dialog.file_path.len() {
-                path_spans.push(Span::styled(
-                    " ",
-                    Style::default().add_modifier(Modifier::REVERSED)
-                ));
-            }
-            
-            lines.push(Spans::from(path_spans));
             lines.push(Spans::from(""));
-            
-            // Show message if any
+
             if let Some(msg) = &dialog.message {
-                lines.push(Spans::from(Span::styled(msg, Style::default().fg(Color::Yellow))));
+                lines.push(Spans::from(Span::styled(msg.clone(), Style::default().fg(Color::Yellow))));
             } else {
-                lines.push(Spans::from("Enter: Save  Esc: Cancel"));
+                lines.push(Spans::from("Empty values are sent as written"));
             }
-            
+            lines.push(Spans::from("Enter: Run  Tab: Next  Ctrl+T: Type  Esc: Cancel"));
+
             let paragraph = Paragraph::new(lines);
             f.render_widget(paragraph, inner);
         }
@@ -1195,6 +2857,143 @@ impl Workspace {
         /* 3️⃣  nothing to run */
         Vec::new()
     }
+
+    /// Where each of `queries` (from `queries_for_execution`) starts in the
+    /// buffer. The statements are trimmed slices of it, so they are found by
+    /// searching forward from the selection or the statement at the caret.
+    fn statement_sources(&self, queries: &[String]) -> Vec<Option<StatementSource>> {
+        let buf = &self.editor.buffer;
+        let mut pos = match self.editor.selection_range() {
+            Some(r) if r.start != r.end => r.start,
+            _ => {
+                let first = queries.first().map(String::as_str).unwrap_or_default();
+                buf.match_indices(first)
+                    .map(|(i, _)| i)
+                    .take_while(|&i| i <= self.editor.caret)
+                    .last()
+                    .unwrap_or(0)
+            }
+        };
+        queries
+            .iter()
+            .map(|q| {
+                let offset = pos + buf.get(pos..)?.find(q.as_str())?;
+                pos = offset + q.len();
+                Some(StatementSource { offset, text: q.clone(), binds: Vec::new() })
+            })
+            .collect()
+    }
+
+    /// Turn a `line N at position M` of statement `query_idx` into a position
+    /// in the editor. Snowflake counts from the `EXECUTE IMMEDIATE $$` line
+    /// when the statement was wrapped, and at the text with bind values
+    /// filled in, which is mapped back onto the placeholders.
+    fn error_location(&self, query_idx: usize, (line, column): (usize, usize)) -> Option<ErrorLocation> {
+        let source = self.run_sources.get(query_idx)?.clone()?;
+        let (sql, _) = self.run_statements.get(query_idx)?;
+        let (line, column) = if self.wrap_script_blocks && Self::should_wrap_statement(sql) {
+            if line <= 1 { (1, 0) } else { (line - 1, column) }
+        } else {
+            (line, column)
+        };
+        let (line, column) = if source.binds.is_empty() {
+            (line, column)
+        } else {
+            bind_vars::original_position(&source.text, sql, &source.binds, (line, column))
+        };
+        Some(ErrorLocation { source, line, column })
+    }
+
+    /// `w` in the results pane: ask where to save the current tab
+    fn save_result_as(&mut self) {
+        let Some(tab) = self.results.tabs.get(self.results.tab_idx) else { return };
+        if !matches!(tab.content, ResultsContent::Table { .. }) {
+            self.status_message = Some("No table loaded, nothing to save.".to_string());
+            self.status_message_time = Some(Instant::now());
+            return;
+        }
+        let name: String = tab.query_context
+            .chars()
+            .map(|c| if c.is_alphanumeric() || c == '-' { c } else { '_' })
+            .collect();
+        let name = name.trim_matches('_');
+        let name = if name.is_empty() { "result" } else { name };
+        let path = get_downloads_folder().join(format!("{}.{}", name, crate::result_file::EXTENSION));
+        self.save_as_dialog = Some(SaveAsDialog::for_result(PathPurpose::SaveResult, path));
+    }
+
+    /// Ctrl+O: ask which `.frostres` file to open
+    fn open_result_as(&mut self) {
+        let mut folder = get_downloads_folder().to_string_lossy().to_string();
+        if !folder.ends_with(std::path::MAIN_SEPARATOR) {
+            folder.push(std::path::MAIN_SEPARATOR);
+        }
+        self.save_as_dialog = Some(SaveAsDialog::for_result(PathPurpose::OpenResult, PathBuf::from(folder)));
+    }
+
+    /// Read a `.frostres` file into a new result tab; no query is run
+    pub fn open_result_file(&mut self, path: &std::path::Path) -> Result<String, String> {
+        let (info, tile_store) = crate::result_file::open(path)
+            .map_err(|e| format!("Could not open {}: {}", path.display(), e))?;
+        let label = path.file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| info.query_context.clone());
+        let msg = format!("Opened {} rows saved {}", info.rows, info.saved_at);
+        self.results.tabs.push(ResultsTab::from_saved(label, info, tile_store));
+        self.results.tab_idx = self.results.tabs.len() - 1;
+        if self.results_hidden {
+            self.results_hidden = false;
+            self.split_offset = self.max_split_offset - 1;
+        }
+        self.focus = Focus::Results;
+        Ok(msg)
+    }
+
+    /// `v` in the results pane: open the cell under the cursor in the inspector
+    fn inspect_cell(&mut self) {
+        match self.results.cursor_cell_value() {
+            Ok((title, text)) => {
+                let (width, height) = crossterm::terminal::size().unwrap_or((80, 24));
+                let screen = Rect { x: 0, y: 0, width, height };
+                self.cell_inspector = Some(CellInspector::new(title, text, screen));
+            }
+            Err(msg) => {
+                self.status_message = Some(msg);
+                self.status_message_time = Some(Instant::now());
+            }
+        }
+    }
+
+    /// `e` on an error tab: put the editor caret where the error points
+    fn jump_to_error(&mut self) {
+        let location = match self.results.tabs.get(self.results.tab_idx).map(|t| &t.content) {
+            Some(ResultsContent::Error { location: Some(loc), .. }) => loc.clone(),
+            Some(ResultsContent::Error { .. }) => {
+                self.status_message = Some("This error doesn't say where in the statement it is".to_string());
+                self.status_message_time = Some(Instant::now());
+                return;
+            }
+            _ => return,
+        };
+        match location.resolve(&self.editor.buffer) {
+            Some(offset) => {
+                self.editor.move_caret_to(offset);
+                if self.editor_hidden {
+                    self.editor_hidden = false;
+                    self.split_offset = self.min_split_offset + 1;
+                }
+                self.focus = Focus::Editor;
+                self.status_message = Some(format!(
+                    "Error at line {}, position {} of the statement",
+                    location.line, location.column
+                ));
+            }
+            None => {
+                self.status_message = Some("The failing statement is no longer in the editor".to_string());
+            }
+        }
+        self.status_message_time = Some(Instant::now());
+    }
     /// Check if auto-wrapping is disabled via comment hint
     fn has_nowrap_hint(stmt: &str) -> bool {
         // Look for --nowrap or /* nowrap */ comment
@@ -1202,6 +3001,77 @@ impl Workspace {
         stmt.contains("/*nowrap*/") || stmt.contains("/* nowrap */")
     }
 
+    /// Value of a `--@name value` hint in the comment lines above the
+    /// statement's SQL; `--@` further down (e.g. in a string) is not a hint
+    fn comment_hint<'a>(stmt: &'a str, name: &str) -> Option<&'a str> {
+        stmt.lines()
+            .map(str::trim)
+            .take_while(|line| line.is_empty() || line.starts_with("--"))
+            .find_map(|line| {
+                let rest = line.strip_prefix("--@")?;
+                let end = rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(rest.len());
+                if !rest[..end].eq_ignore_ascii_case(name) {
+                    return None;
+                }
+                // a hint without a value is reported like a bad one
+                Some(rest[end..].split_whitespace().next().unwrap_or(""))
+            })
+    }
+
+    /// Row cap from a `--@limit N` comment hint (`0` means no cap)
+    pub(crate) fn fetch_limit_hint(stmt: &str) -> Result<Option<usize>, String> {
+        let Some(value) = Self::comment_hint(stmt, "limit") else {
+            return Ok(None);
+        };
+        value
+            .replace('_', "")
+            .parse()
+            .map(Some)
+            .map_err(|_| format!("Invalid --@limit hint '{}': expected a row count such as 5000", value))
+    }
+
+    /// `USE …`, and creating or dropping a database or schema (which moves the
+    /// session into or out of it), change the current context. Script blocks
+    /// count if they run a `USE`.
+    pub(crate) fn changes_session_context(stmt: &str) -> bool {
+        let upper = stmt.to_ascii_uppercase();
+        let words: Vec<&str> = upper
+            .lines()
+            .map(|line| line.split("--").next().unwrap_or(""))
+            .flat_map(|line| line.split(|c: char| c.is_whitespace() || c == ';'))
+            .filter(|w| !w.is_empty())
+            .collect();
+        match words.first().copied() {
+            Some("USE") => true,
+            Some("CREATE" | "DROP") => words.iter().take(6).any(|&w| w == "DATABASE" || w == "SCHEMA"),
+            Some("EXECUTE" | "BEGIN" | "DECLARE") => words.contains(&"USE"),
+            _ => false,
+        }
+    }
+
+    /// Timeout from a `--@timeout 90` / `30s` / `5m` / `1h` comment hint (`0` means none)
+    pub(crate) fn statement_timeout_hint(stmt: &str) -> Result<Option<Duration>, String> {
+        let Some(hint) = Self::comment_hint(stmt, "timeout") else {
+            return Ok(None);
+        };
+        let value = hint.to_ascii_lowercase();
+        let (num, unit) = match value.find(|c: char| !c.is_ascii_digit()) {
+            Some(pos) => value.split_at(pos),
+            None => (value.as_str(), "s"),
+        };
+        let scale = match unit {
+            "s" => Some(1),
+            "m" => Some(60),
+            "h" => Some(3600),
+            _ => None,
+        };
+        scale
+            .zip(num.parse::<u64>().ok())
+            .and_then(|(scale, n)| n.checked_mul(scale))
+            .map(|secs| Some(Duration::from_secs(secs)))
+            .ok_or_else(|| format!("Invalid --@timeout hint '{}': expected seconds or a duration such as 30s, 10m or 1h", hint))
+    }
+
     /// Find the complete DECLARE/BEGIN/END block containing the given position
     fn find_enclosing_block(buf: &str, pos: usize) -> Option<(usize, usize)> {
         // Helper to check if a position has a keyword
@@ -1817,7 +3687,7 @@ pub fn render<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> {
                 self.last_results_area = Some(chunks[0]);
                 self.results.max_rows = chunks[0].height.saturating_sub(4).max(1) as usize;
                 self.results.max_cols = (chunks[0].width / 20).max(2) as usize;
-                self.results.render(f, chunks[0], self.total_queries);
+                self.results.render(f, chunks[0], self.tab_total());
             }
             
             // Return status bar location
@@ -1865,7 +3735,7 @@ pub fn render<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> {
             
             // NEW: Only render results if visible
             if !self.results_hidden {
-                self.results.render(f, chunks[1], self.total_queries);
+                self.results.render(f, chunks[1], self.tab_total());
             }
             
             // Return status bar location
@@ -1874,42 +3744,82 @@ pub fn render<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> {
 
         // ── Render status bar (single location for all cases) ────────────
         // helper-closure for the old fallback text
-        let default_status = |msg: &Option<String>, err: &Option<String>, connected: bool| -> (String, tui::style::Style) {
-            let conn_status = if connected { "[Connected]" } else { "[Not Connected]" };
+        let run_summary = &self.run_summary;
+        let default_status = |msg: &Option<String>, err: &Option<String>, connection: &ConnectionState| -> (String, tui::style::Style) {
+            let conn_status = connection.label();
             if let Some(m) = msg {
-                (format!("{} | {}", conn_status, m), STYLE::status_fg())
-            } else if let Some(e) = err {
-                (format!("{} | Press F1 for help | Error: {}", conn_status, e), STYLE::status_fg())
-            } else {
-                (format!("{} | Press F1 for help", conn_status), STYLE::status_fg())
+                return (format!("{} | {}", conn_status, m), STYLE::status_fg());
+            }
+            let summary = run_summary.as_ref().map(|s| format!(" | {}", s)).unwrap_or_default();
+            let error = err.as_ref().map(|e| format!(" | Error: {}", e)).unwrap_or_default();
+            (format!("{}{} | Press F1 for help{}", conn_status, summary, error), STYLE::status_fg())
+        };
+        // Active profile badge, drawn in the profile's accent colour
+        use tui::text::{Span, Spans};
+        let badge = self.active_profile.as_ref().map(|name| {
+            let style = match self.profiles.get(name).and_then(|p| p.accent) {
+                Some(accent) => Style::default().fg(Color::Rgb(22, 22, 22)).bg(crate::palette::rgb(accent)),
+                None => STYLE::status_fg().add_modifier(tui::style::Modifier::REVERSED),
+            };
+            Span::styled(format!(" {} ", name), style)
+        });
+        // Manual-commit indicator; highlighted once statements are pending
+        let txn_badge = self.transaction.map(|n| match n {
+            0 => Span::styled(" MANUAL COMMIT ", STYLE::status_fg().add_modifier(tui::style::Modifier::REVERSED)),
+            n => Span::styled(
+                format!(" OPEN TXN · {} stmt{} ", n, if n == 1 { "" } else { "s" }),
+                Style::default().fg(Color::Rgb(22, 22, 22)).bg(Color::Yellow),
+            ),
+        });
+        let continue_badge = self.continue_on_error
+            .then(|| Span::styled(" CONTINUE ON ERROR ", STYLE::status_fg().add_modifier(tui::style::Modifier::REVERSED)));
+        // Session context from the worker: role · warehouse · database.schema
+        let context_span = self.session.as_ref().map(|c| Span::styled(format!(" {} ", c.label()), STYLE::info_fg()));
+        let with_badge = |spans: Vec<Span<'static>>| -> Spans<'static> {
+            let mut all = Vec::new();
+            if let Some(b) = &badge {
+                all.push(b.clone());
+                all.push(Span::raw(" "));
             }
+            if let Some(b) = &txn_badge {
+                all.push(b.clone());
+                all.push(Span::raw(" "));
+            }
+            if let Some(b) = &continue_badge {
+                all.push(b.clone());
+                all.push(Span::raw(" "));
+            }
+            if let Some(c) = &context_span {
+                all.push(c.clone());
+                all.push(Span::raw("| "));
+            }
+            all.extend(spans);
+            Spans::from(all)
         };
         // Check if we should show results selection summary
         if self.focus == Focus::Results {
             if let Some((stats, warn)) = self.results.selection_stats() {
-                use tui::text::{Span, Spans};
-
                 let spans = if let Some(w) = warn {
-                    Spans::from(vec![
+                    with_badge(vec![
                         Span::raw(stats),
                         Span::raw("  |  "),
                         Span::styled(w, STYLE::error_fg()),   // red only for warning
                     ])
                 } else {
-                    Spans::from(Span::raw(stats))
+                    with_badge(vec![Span::raw(stats)])
                 };
                 let bar = tui::widgets::Paragraph::new(spans).style(STYLE::status_fg());
                 f.render_widget(bar, status_chunk);
             } else {
                 // No selection stats, show default status
-                let (txt, style) = default_status(&self.status_message, &self.error, self.connected);
-                let bar = tui::widgets::Paragraph::new(txt).style(style);
+                let (txt, style) = default_status(&self.status_message, &self.error, &self.connection);
+                let bar = tui::widgets::Paragraph::new(with_badge(vec![Span::raw(txt)])).style(style);
                 f.render_widget(bar, status_chunk);
              }
         } else {
             // Not focused on results, show default status
-            let (txt, style) = default_status(&self.status_message, &self.error, self.connected);
-            let bar = tui::widgets::Paragraph::new(txt).style(style);
+            let (txt, style) = default_status(&self.status_message, &self.error, &self.connection);
+            let bar = tui::widgets::Paragraph::new(with_badge(vec![Span::raw(txt)])).style(style);
             f.render_widget(bar, status_chunk);
         }
         /* ── 5️⃣  Batch generator overlay? ─────────────────────────────── */
@@ -1921,6 +3831,36 @@ pub fn render<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> {
         if self.save_as_dialog.is_some() {
             self.render_save_as_dialog(f, size);
         }
+
+        /* ── Profile picker overlay? ─────────────────────────────── */
+        if self.profile_picker.is_some() {
+            self.render_profile_picker(f, size);
+        }
+
+        /* ── History browser overlay? ─────────────────────────────── */
+        if self.history_browser.is_some() {
+            self.render_history_browser(f, size);
+        }
+
+        /* ── Open transaction prompt? ─────────────────────────────── */
+        if self.txn_prompt.is_some() {
+            self.render_txn_prompt(f, size);
+        }
+
+        /* ── Bind variables overlay? ─────────────────────────────── */
+        if self.bind_dialog.is_some() {
+            self.render_bind_dialog(f, size);
+        }
+
+        /* ── Cell inspector overlay? ─────────────────────────────── */
+        if self.cell_inspector.is_some() {
+            self.render_cell_inspector(f, size);
+        }
+
+        /* ── Connection secret prompt? ─────────────────────────────── */
+        if self.secret_prompt.is_some() {
+            self.render_secret_prompt(f, size);
+        }
     })?;
     Ok(())
 }
@@ -1933,6 +3873,10 @@ pub fn render<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> {
 
     /// Request to exit the application
     pub fn request_exit(&mut self) -> bool {
+        if self.has_open_transaction() {
+            self.txn_prompt = Some(AfterTransaction::Exit);
+            return false;
+        }
         if self.has_unsaved_changes() {
             self.show_exit_dialog = true;
             self.exit_requested = true;
@@ -1946,7 +3890,10 @@ pub fn render<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> {
         // Handle Ctrl+Q with confirmation
         if (key.code == KeyCode::Char('q') || key.code == KeyCode::Char('Q')) 
             && key.modifiers.contains(KeyModifiers::CONTROL) {
-            if self.has_unsaved_changes() {
+            if self.has_open_transaction() {
+                self.txn_prompt = Some(AfterTransaction::Exit);
+                return Ok(false);
+            } else if self.has_unsaved_changes() {
                 self.show_exit_dialog = true;
                 return Ok(false);
             } else {
@@ -1954,6 +3901,28 @@ pub fn render<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> {
             }
         }
 
+        // Handle the open-transaction prompt (exit / profile switch)
+        if self.txn_prompt.is_some() {
+            if key.kind != KeyEventKind::Press {
+                return Ok(false);
+            }
+            match key.code {
+                KeyCode::Char('c') | KeyCode::Char('C') => {
+                    self.after_transaction = self.txn_prompt.take();
+                    self.commit_transaction();
+                }
+                KeyCode::Char('r') | KeyCode::Char('R') => {
+                    self.after_transaction = self.txn_prompt.take();
+                    self.rollback_transaction();
+                }
+                KeyCode::Esc => {
+                    self.txn_prompt = None;
+                }
+                _ => {}
+            }
+            return Ok(false);
+        }
+
         // Handle exit dialog
         if self.show_exit_dialog {
             match key.code {
@@ -2064,6 +4033,29 @@ pub fn render<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> {
                 should_close = !dialog.active;
             }
             
+            let purpose = self.save_as_dialog.as_ref().map_or(PathPurpose::SaveSql, |d| d.purpose);
+            let result_file = match (&save_path, purpose) {
+                (Some(path), PathPurpose::SaveResult) => Some(self.results.save_current_tab(path)),
+                (Some(path), PathPurpose::OpenResult) => Some(self.open_result_file(path)),
+                _ => None,
+            };
+            match result_file {
+                Some(Ok(msg)) => {
+                    self.status_message = Some(msg);
+                    self.status_message_time = Some(Instant::now());
+                    self.save_as_dialog = None;
+                    return Ok(false);
+                }
+                Some(Err(msg)) => {
+                    if let Some(dialog) = &mut self.save_as_dialog {
+                        dialog.active = true;
+                        dialog.message = Some(msg);
+                    }
+                    return Ok(false);
+                }
+                None => {}
+            }
+
             // Handle save operation outside the borrow
             if let Some(path) = save_path {
                 match std::fs::write(&path, &self.editor.buffer) {
@@ -2103,17 +4095,138 @@ pub fn render<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> {
                     self.exit_requested = false;
                 }
             }
-            
+            
+            return Ok(false);
+        }
+
+        // Handle profile picker
+        if self.profile_picker.is_some() {
+            if key.kind != KeyEventKind::Press {
+                return Ok(false);
+            }
+            let mut chosen = None;
+            if let Some(dialog) = &mut self.profile_picker {
+                chosen = dialog.handle_key(key);
+                if !dialog.active {
+                    self.profile_picker = None;
+                }
+            }
+            if let Some(name) = chosen {
+                if self.active_profile.as_deref() != Some(name.as_str()) {
+                    if self.has_open_transaction() {
+                        self.txn_prompt = Some(AfterTransaction::SwitchProfile(name));
+                    } else {
+                        self.switch_profile(&name);
+                    }
+                }
+            }
+            return Ok(false);
+        }
+
+        // Handle history browser
+        if self.history_browser.is_some() {
+            if key.kind != KeyEventKind::Press {
+                return Ok(false);
+            }
+            let mut action = None;
+            if let Some(browser) = &mut self.history_browser {
+                action = browser.handle_key(key);
+                if !browser.active {
+                    self.history_browser = None;
+                }
+            }
+            match action {
+                Some(HistoryAction::Insert(sql)) => {
+                    self.editor.insert(&sql);
+                    self.focus = Focus::Editor;
+                }
+                Some(HistoryAction::Run(sql)) => self.run_queries(vec![sql], Vec::new(), false),
+                None => {}
+            }
+            return Ok(false);
+        }
+
+        // Handle the cell inspector
+        if self.cell_inspector.is_some() {
+            if key.kind != KeyEventKind::Press {
+                return Ok(false);
+            }
+            match self.cell_inspector.as_mut().and_then(|inspector| inspector.handle_key(key)) {
+                Some(InspectorAction::Close) => self.cell_inspector = None,
+                Some(InspectorAction::Copy) => {
+                    use copypasta::ClipboardProvider;
+                    let text = self.cell_inspector.as_ref().map(|i| i.text.clone()).unwrap_or_default();
+                    let msg = match self.results.clipboard.set_contents(text) {
+                        Ok(_) => "Copied the cell value".to_string(),
+                        Err(e) => format!("Could not copy the value: {}", e),
+                    };
+                    self.status_message = Some(msg);
+                    self.status_message_time = Some(Instant::now());
+                }
+                None => {}
+            }
+            return Ok(false);
+        }
+
+        // Handle the connection's secret prompt
+        if self.secret_prompt.is_some() {
+            if key.kind != KeyEventKind::Press {
+                return Ok(false);
+            }
+            let done = self.secret_prompt.as_mut().and_then(|dialog| dialog.handle_key(key));
+            match done {
+                Some(true) => {
+                    self.secret_prompt = None;
+                    let _ = self.db_req_tx.send(DbWorkerRequest::Reconnect);
+                    self.status_message = Some("Connecting…".to_string());
+                    self.status_message_time = Some(Instant::now());
+                }
+                Some(false) => {
+                    self.secret_prompt = None;
+                    self.status_message = Some("Not connected – F5 asks again".to_string());
+                    self.status_message_time = Some(Instant::now());
+                }
+                None => {}
+            }
             return Ok(false);
         }
 
-        // Block ctrl+enter only during running!
-        if self.running
-           && key.kind == KeyEventKind::Press
-           && key.code == KeyCode::Enter
-           && key.modifiers.contains(KeyModifiers::CONTROL)
-        {
-            return Ok(false);          // ignore repeated Ctrl-Enter
+        // Handle bind variables dialog
+        if self.bind_dialog.is_some() {
+            if key.kind != KeyEventKind::Press {
+                return Ok(false);
+            }
+            let mut submitted = None;
+            if let Some(dialog) = &mut self.bind_dialog {
+                let literals = dialog.handle_key(key);
+                if !dialog.active {
+                    for (name, value) in dialog.names.iter().zip(&dialog.values) {
+                        self.bind_values.insert(name.clone(), value.clone());
+                    }
+                    submitted = literals.map(|l| {
+                        (std::mem::take(&mut dialog.queries), std::mem::take(&mut dialog.sources), l, dialog.parallel)
+                    });
+                    self.bind_dialog = None;
+                }
+            }
+            match submitted {
+                Some((queries, mut sources, literals, parallel)) => {
+                    let queries = queries.iter().enumerate().map(|(i, q)| {
+                        let (sql, binds) = bind_vars::substitute(q, &literals);
+                        if let Some(Some(source)) = sources.get_mut(i) {
+                            source.binds = binds;
+                        }
+                        sql
+                    }).collect();
+                    self.run_queries(queries, sources, parallel);
+                }
+                None if self.bind_dialog.is_none() => {
+                    self.status_message = Some("Run cancelled".to_string());
+                    self.status_message_time = Some(Instant::now());
+                }
+                None => {}
+            }
+            return Ok(false);
         }
 
         // ── ESC  ───────────────────────────────────────────────
@@ -2140,20 +4253,26 @@ pub fn render<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> {
                     self.start_execute()?;
                     return Ok(false);
                 }
+                KeyCode::Char('o') | KeyCode::Char('O') => {   // Ctrl-O → open a saved result
+                    self.open_result_as();
+                    return Ok(false);
+                }
                 KeyCode::Backspace => {        // Ctrl-Backspace → cancel
                     if self.running {
-                        // ① Call SQLCancel immediately on whatever is running
-                        if let Some(h) = *self.current_stmt.lock().unwrap() {
-                            unsafe { let _ = SQLCancel(h.0); };
-                        }
+                        // ① Cancel immediately whatever is running
+                        self.canceller.cancel();
                         // ② (Optionally) still poke the worker so it knows we intended to cancel.
                         let _ = self.db_req_tx.send(DbWorkerRequest::Cancel);
 
                         // ③ Update local UI state
                         self.running = false;
                         self.running_query_idx = None;
+                        self.parallel_remaining = None;
                         self.run_duration = self.run_started.map(|s| s.elapsed());
-                        self.status_message = Some("Query cancellation requested…".into());
+                        self.status_message = Some(match self.run_queue.len() {
+                            0 => "Query cancellation requested…".to_string(),
+                            n => format!("Query cancellation requested… ({} queued run(s) follow – x removes them)", n),
+                        });
                         self.status_message_time = Some(Instant::now());
                     }
                     return Ok(false);
@@ -2168,9 +4287,67 @@ pub fn render<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> {
             return Ok(false);
         }
 
+        if key.kind == KeyEventKind::Press && key.code == KeyCode::F(2) {
+            if self.profiles.is_empty() {
+                self.status_message = Some("No [profiles.<name>] entries in Frost.toml".to_string());
+                self.status_message_time = Some(Instant::now());
+            } else {
+                let names = self.profiles.keys().cloned().collect();
+                self.profile_picker = Some(ProfilePickerDialog::new(names, self.active_profile.as_deref()));
+            }
+            return Ok(false);
+        }
+
+        if key.kind == KeyEventKind::Press && key.code == KeyCode::F(3) {
+            match self.history.as_ref().map(|h| h.recent(crate::query_history::BROWSER_LIMIT)) {
+                Some(Ok(entries)) => self.history_browser = Some(HistoryBrowser::new(entries)),
+                Some(Err(e)) => {
+                    self.status_message = Some(format!("Failed to read query history: {}", e));
+                    self.status_message_time = Some(Instant::now());
+                }
+                None => {
+                    self.status_message = Some("Query history is unavailable".to_string());
+                    self.status_message_time = Some(Instant::now());
+                }
+            }
+            return Ok(false);
+        }
+
+        if key.kind == KeyEventKind::Press && key.code == KeyCode::F(4) {
+            self.start_parallel_execute()?;
+            return Ok(false);
+        }
+
+        if key.kind == KeyEventKind::Press && key.code == KeyCode::F(5) {
+            self.reconnect();
+            return Ok(false);
+        }
+
+        if key.kind == KeyEventKind::Press && key.code == KeyCode::F(6) {
+            self.toggle_transaction_mode();
+            return Ok(false);
+        }
+
+        if key.kind == KeyEventKind::Press && key.code == KeyCode::F(7) {
+            self.commit_transaction();
+            return Ok(false);
+        }
+
+        if key.kind == KeyEventKind::Press && key.code == KeyCode::F(8) {
+            self.rollback_transaction();
+            return Ok(false);
+        }
+
+        if key.kind == KeyEventKind::Press && key.code == KeyCode::F(12) {
+            self.toggle_continue_on_error();
+            return Ok(false);
+        }
+
         if key.kind == KeyEventKind::Press && key.code == KeyCode::F(10) {
             if let Some(path) = &self.file_path {
-                self.batch_generator = Some(BatchGeneratorDialog::new(path.clone()));
+                let mut dialog = BatchGeneratorDialog::new(path.clone());
+                dialog.profile = self.active_profile.clone();
+                self.batch_generator = Some(dialog);
             } else {
                 self.status_message = Some("Save the file first to generate a batch script".to_string());
                 self.status_message_time = Some(Instant::now());
@@ -2310,7 +4487,52 @@ pub fn render<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> {
                 }
             }
             Focus::Results => {
-                self.results.handle_key(key);
+                let plain = !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+                match key.code {
+                    KeyCode::Char(c @ ('n' | 'N')) if plain && key.kind == KeyEventKind::Press && !self.results.find_active => {
+                        self.fetch_more(c == 'N');
+                    }
+                    // 📋 x closes the tab (or drops a queued run), X clears finished tabs,
+                    // < / > move a queued run
+                    KeyCode::Char('x') if plain && key.kind == KeyEventKind::Press && !self.results.find_active => {
+                        self.close_result_tab();
+                    }
+                    KeyCode::Char('X') if plain && key.kind == KeyEventKind::Press && !self.results.find_active => {
+                        self.clear_finished_tabs();
+                    }
+                    KeyCode::Char(c @ ('<' | '>')) if plain && key.kind == KeyEventKind::Press && !self.results.find_active => {
+                        self.move_queued_run(c == '>');
+                    }
+                    // ⚠ { / } step through error and timed-out tabs
+                    KeyCode::Char(c @ ('{' | '}')) if plain && key.kind == KeyEventKind::Press && !self.results.find_active => {
+                        let msg = match self.results.jump_to_failed_tab(c == '}') {
+                            Ok(m) | Err(m) => m,
+                        };
+                        self.status_message = Some(msg);
+                        self.status_message_time = Some(Instant::now());
+                    }
+                    // 🆔 i copies the statement's query ID
+                    KeyCode::Char('i') if plain && key.kind == KeyEventKind::Press && !self.results.find_active => {
+                        let msg = match self.results.copy_query_id() {
+                            Ok(m) | Err(m) => m,
+                        };
+                        self.status_message = Some(msg);
+                        self.status_message_time = Some(Instant::now());
+                    }
+                    // 💾 w saves the tab as a .frostres file
+                    KeyCode::Char('w') if plain && key.kind == KeyEventKind::Press && !self.results.find_active => {
+                        self.save_result_as();
+                    }
+                    // 🔍 v opens the cell under the cursor in full
+                    KeyCode::Char('v') if plain && key.kind == KeyEventKind::Press && !self.results.find_active => {
+                        self.inspect_cell();
+                    }
+                    // 🎯 e on an error tab jumps to the failing line in the editor
+                    KeyCode::Char('e') if plain && key.kind == KeyEventKind::Press && !self.results.find_active => {
+                        self.jump_to_error();
+                    }
+                    _ => self.results.handle_key(key),
+                }
             }
             Focus::DbTree => {
                 // Tree handles its own keys
@@ -2562,10 +4784,20 @@ pub fn render<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> {
     }
 
     pub fn start_execute(&mut self) -> Result<()> {
+        self.execute_statements(false)
+    }
+
+    /// Run the selected statements side by side on pooled connections (F4)
+    pub fn start_parallel_execute(&mut self) -> Result<()> {
+        self.execute_statements(true)
+    }
+
+    fn execute_statements(&mut self, parallel: bool) -> Result<()> {
         // Save any pending edits before executing
         self.editor.on_focus_lost();
         
         let queries = self.queries_for_execution();
+        let sources = self.statement_sources(&queries);
 
         if queries.is_empty() {
             self.status_message = Some("No SQL statement at caret/selection.".into());
@@ -2573,11 +4805,53 @@ pub fn render<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> {
             return Ok(());                                    // bail out early
         }
 
+        // Ask for placeholder values first; the dialog runs the statements
+        let unbound = bind_vars::unbound_placeholders(&queries);
+        if !unbound.is_empty() {
+            let mut dialog = BindVariablesDialog::new(unbound, &self.bind_values, queries, self.backslash_escapes);
+            dialog.sources = sources;
+            dialog.parallel = parallel;
+            self.bind_dialog = Some(dialog);
+            return Ok(());
+        }
+
+        self.run_queries(queries, sources, parallel);
+        Ok(())
+    }
+
+    /// `sources` is where the statements are in the editor, if they came from it
+    fn run_queries(&mut self, queries: Vec<String>, sources: Vec<Option<StatementSource>>, parallel: bool) {
+        if self.worker_busy {
+            self.run_queue.push_back(QueuedRun { queries, sources, parallel });
+            self.sync_queued_tabs();
+            self.status_message = Some(format!(
+                "Queued as #{} – runs when the current query is done",
+                self.run_queue.len()
+            ));
+            self.status_message_time = Some(Instant::now());
+            return;
+        }
+        if let ConnectionState::Disconnected { message, retry_at: None } = &self.connection {
+            self.status_message = Some(format!(
+                "Not connected ({}) – press F5 to reconnect",
+                message.lines().next().unwrap_or("")
+            ));
+            self.status_message_time = Some(Instant::now());
+            return;
+        }
+        if parallel && self.transaction.is_some() {
+            // pooled connections would run outside the open transaction
+            self.status_message = Some("Parallel runs need autocommit – press F6 to leave manual commit mode".to_string());
+            self.status_message_time = Some(Instant::now());
+            return;
+        }
+
         // Wrap queries that need it
         let wrapped_queries: Vec<String> = queries
-            .into_iter()
+            .iter()
+            .cloned()
             .map(|q| {
-                if Self::should_wrap_statement(&q) {
+                if self.wrap_script_blocks && Self::should_wrap_statement(&q) {
                     format!("EXECUTE IMMEDIATE $$\n{}\n$$;", q.trim_end_matches(';'))
                 } else {
                     q
@@ -2594,37 +4868,51 @@ pub fn render<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> {
             })
             .collect();
 
-        self.results.clear();
-        self.error = None;
-        self.running = true;
-        self.run_started = Some(Instant::now());
-        self.run_duration = None;
-        self.running_query_idx = None;
-        self.total_queries = queries_with_context.len();
-
-        let _ = self.db_req_tx.send(DbWorkerRequest::RunQueries(queries_with_context));
-        Ok(())
+        self.begin_run(queries
+            .into_iter()
+            .zip(queries_with_context.iter().map(|(_, context)| context.clone()))
+            .collect());
+        self.run_sources = sources;
+        self.parallel_remaining = parallel.then_some(self.total_queries);
+
+        if parallel {
+            let connections = self.total_queries.min(self.worker_options.parallel_connections.max(1));
+            self.status_message = Some(format!(
+                "Running {} statement(s) on up to {} connection(s)",
+                self.total_queries, connections
+            ));
+            self.status_message_time = Some(Instant::now());
+            let _ = self.db_req_tx.send(DbWorkerRequest::RunParallel(queries_with_context));
+        } else {
+            let _ = self.db_req_tx.send(DbWorkerRequest::RunQueries {
+                queries: queries_with_context,
+                continue_on_error: self.run_continues,
+            });
+        }
     }
 
     /// Execute role change commands without adding them to the editor
     fn execute_role_change(&mut self, queries: Vec<String>) -> Result<()> {
+        if self.worker_busy {
+            self.status_message = Some("Wait for the running query to finish before changing role".to_string());
+            self.status_message_time = Some(Instant::now());
+            return Ok(());
+        }
+
         // Prepare queries with context
         let queries_with_context: Vec<(String, String)> = queries
             .into_iter()
             .map(|q| (q, "Role Change".to_string()))
             .collect();
 
-        // Clear any existing results
-        self.results.clear();
-        self.error = None;
-        self.running = true;
-        self.run_started = Some(Instant::now());
-        self.run_duration = None;
-        self.running_query_idx = None;
-        self.total_queries = queries_with_context.len();
+        self.begin_run(queries_with_context.clone());
+        self.parallel_remaining = None;
 
         // Send to worker
-        let _ = self.db_req_tx.send(DbWorkerRequest::RunQueries(queries_with_context));
+        let _ = self.db_req_tx.send(DbWorkerRequest::RunQueries {
+            queries: queries_with_context,
+            continue_on_error: self.run_continues,
+        });
         
         // Show status
         self.status_message = Some("Changing role...".to_string());
@@ -2633,6 +4921,307 @@ pub fn render<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> {
         Ok(())
     }
 
+    /// Reset the run state for `statements` (as written, with their context).
+    /// Tabs of earlier runs stay; the new run's tabs go after them.
+    fn begin_run(&mut self, statements: Vec<(String, String)>) {
+        for tab in &mut self.results.tabs {
+            tab.query_idx = None;
+            if tab.truncated == Some(Truncation::Resumable) {
+                tab.truncated = Some(Truncation::Closed);  // the new run closes the parked cursor
+            }
+        }
+        self.trim_kept_tabs();
+        self.error = None;
+        self.run_summary = None;
+        self.run_continues = self.continue_on_error;
+        self.running = true;
+        self.worker_busy = true;
+        self.run_started = Some(Instant::now());
+        self.run_duration = None;
+        self.running_query_idx = None;
+        self.total_queries = statements.len();
+        self.history_ids = vec![None; self.total_queries];
+        self.run_statements = statements;
+        self.run_sources = Vec::new();
+    }
+
+    /// Start queued runs now that the worker is idle. A run that can't start
+    /// (e.g. while disconnected) is dropped with a status message.
+    fn start_queued_run(&mut self) {
+        while !self.worker_busy {
+            let Some(run) = self.run_queue.pop_front() else { break };
+            self.sync_queued_tabs();
+            self.run_queries(run.queries, run.sources, run.parallel);
+        }
+    }
+
+    /// Rebuild the queued tabs, which always come last, from `run_queue`
+    fn sync_queued_tabs(&mut self) {
+        let viewed_queue_pos = self.results.tabs.get(self.results.tab_idx)
+            .and_then(|t| match t.content {
+                ResultsContent::Queued { position, .. } => Some(position),
+                _ => None,
+            });
+
+        self.results.tabs.retain(|t| !t.is_queued());
+        let live = self.results.tabs.len();
+        for (i, run) in self.run_queue.iter().enumerate() {
+            let mut context = run.queries.first().map(|q| Self::extract_query_context(q)).unwrap_or_default();
+            if run.queries.len() > 1 {
+                context.push_str(&format!(" +{}", run.queries.len() - 1));
+            }
+            if run.parallel {
+                context.push_str(" (parallel)");
+            }
+            let sql = run.queries.join(";\n");
+            self.results.tabs.push(ResultsTab::new_queued(context, i + 1, sql));
+        }
+
+        // stay on the same queue position if a queued tab was shown
+        if let Some(pos) = viewed_queue_pos {
+            self.results.tab_idx = live + pos.min(self.run_queue.len()).saturating_sub(1);
+        }
+        if self.results.tab_idx >= self.results.tabs.len() {
+            self.results.tab_idx = self.results.tabs.len().saturating_sub(1);
+        }
+    }
+
+    /// `x` in the results pane: drop the queued run or close the finished tab
+    fn close_result_tab(&mut self) {
+        let idx = self.results.tab_idx;
+        let Some(tab) = self.results.tabs.get(idx) else { return };
+        let live = self.results.tabs.len() - self.run_queue.len();
+        let message = if let ResultsContent::Queued { position, .. } = tab.content {
+            self.run_queue.remove(position - 1);
+            self.sync_queued_tabs();
+            format!("Removed queued run #{}", position)
+        } else if tab.running || (self.worker_busy && tab.query_idx.is_some()) {
+            "The current run is still using this tab – cancel it first (Ctrl+Backspace)".to_string()
+        } else {
+            self.results.tabs.remove(idx);
+            if idx + 1 >= live && idx > 0 {
+                self.results.tab_idx = idx - 1;
+            }
+            "Result tab closed".to_string()
+        };
+        self.status_message = Some(message);
+        self.status_message_time = Some(Instant::now());
+    }
+
+    /// `X` in the results pane: close every tab not used by the current run or the queue
+    fn clear_finished_tabs(&mut self) {
+        let busy = self.worker_busy;
+        let before = self.results.tabs.len();
+        self.results.tabs.retain(|t| t.is_queued() || t.running || (busy && t.query_idx.is_some()));
+        self.results.tab_idx = 0;
+        self.status_message = Some(format!("Closed {} result tab(s)", before - self.results.tabs.len()));
+        self.status_message_time = Some(Instant::now());
+    }
+
+    /// Close the oldest tabs of earlier runs past `MAX_KEPT_RESULT_TABS`;
+    /// their rows and temp files go with them
+    fn trim_kept_tabs(&mut self) {
+        let kept = |t: &ResultsTab| t.query_idx.is_none() && !t.running && !t.is_queued();
+        let mut excess = self.results.tabs.iter().filter(|t| kept(t)).count().saturating_sub(MAX_KEPT_RESULT_TABS);
+        if excess == 0 {
+            return;
+        }
+        let viewed = self.results.tab_idx;
+        let mut closed_before_viewed = 0;
+        let mut idx = 0;
+        self.results.tabs.retain(|t| {
+            let close = excess > 0 && kept(t);
+            if close {
+                excess -= 1;
+                closed_before_viewed += (idx < viewed) as usize;
+            }
+            idx += 1;
+            !close
+        });
+        self.results.tab_idx = viewed
+            .saturating_sub(closed_before_viewed)
+            .min(self.results.tabs.len().saturating_sub(1));
+    }
+
+    /// `<` / `>` on a queued tab: run it earlier or later
+    fn move_queued_run(&mut self, later: bool) {
+        let Some(ResultsContent::Queued { position, .. }) = self.results.tabs.get(self.results.tab_idx).map(|t| &t.content) else {
+            return;
+        };
+        let from = position - 1;
+        let to = if later { from + 1 } else { from.wrapping_sub(1) };
+        if to >= self.run_queue.len() {
+            return;
+        }
+        self.run_queue.swap(from, to);
+        self.results.tab_idx = self.results.tab_idx + to - from;
+        self.sync_queued_tabs();
+    }
+
+    /// Denominator of the "i/N" tab labels: the finished and running tabs plus
+    /// statements of the current run that haven't started yet
+    fn tab_total(&self) -> usize {
+        let live = self.results.tabs.len() - self.run_queue.len();
+        let started = self.results.tabs.iter()
+            .filter_map(|t| t.query_idx)
+            .collect::<std::collections::HashSet<_>>()
+            .len();
+        live + if self.running { self.total_queries.saturating_sub(started) } else { 0 }
+    }
+
+    /// The tab of statement `query_idx` of the current run; the latest one
+    /// when the statement returned several result sets
+    fn run_tab(&mut self, query_idx: usize) -> Option<&mut ResultsTab> {
+        self.results.tabs.iter_mut().rev().find(|t| t.query_idx == Some(query_idx))
+    }
+
+    /// Replace the live view of a closed parked cursor with its sealed store
+    fn adopt_sealed(&mut self, path: &std::path::Path, result: ResultsContent) {
+        let parked = self.results.tabs.iter_mut().find(|t| matches!(
+            &t.content,
+            ResultsContent::Table { tile_store, .. } if tile_store.is_live() && tile_store.path() == path
+        ));
+        if let Some(tab) = parked {
+            tab.content = result;
+            tab.truncated = Some(Truncation::Closed);
+            tab.summary_cache = None;
+        }
+    }
+
+    /// Another result set of statement `query_idx` is on its way: the current
+    /// tab is complete and a new one follows right after it
+    fn add_result_set_tab(&mut self, query_idx: usize, result: ResultsContent, query_id: Option<String>) {
+        let Some(pos) = self.results.tabs.iter().rposition(|t| t.query_idx == Some(query_idx)) else { return };
+        let tab = &mut self.results.tabs[pos];
+        tab.content = result;
+        tab.query_id = query_id;
+        tab.elapsed = tab.run_started.map(|s| s.elapsed());
+        tab.running = false;
+        tab.run_started = None;
+        tab.rows_fetched = None;
+        tab.summary_cache = None;
+
+        let mut next = ResultsTab::new_pending(tab.query_context.clone());
+        next.query_idx = Some(query_idx);
+        next.sql = tab.sql.clone();
+        self.results.tabs.insert(pos + 1, next);
+        if self.results.tab_idx == pos {
+            self.results.tab_idx = pos + 1;  // follow the statement's output
+        } else if self.results.tab_idx > pos {
+            self.results.tab_idx += 1;
+        }
+        self.running_query_idx = Some(pos + 1);
+        self.label_result_sets(query_idx);
+    }
+
+    /// "CALL MY_PROC (2/3)" for statements with more than one result set
+    fn label_result_sets(&mut self, query_idx: usize) {
+        let Some((_, context)) = self.run_statements.get(query_idx) else { return };
+        let context = context.clone();
+        let count = self.results.tabs.iter().filter(|t| t.query_idx == Some(query_idx)).count();
+        if count < 2 {
+            return;
+        }
+        for (n, tab) in self.results.tabs.iter_mut().filter(|t| t.query_idx == Some(query_idx)).enumerate() {
+            tab.query_context = format!("{} ({}/{})", context, n + 1, count);
+        }
+    }
+
+    fn result_row_count(result: &ResultsContent) -> Option<usize> {
+        match result {
+            ResultsContent::Table { tile_store, .. } => Some(tile_store.nrows),
+            _ => None,
+        }
+    }
+
+    /// A statement reached its final state. A serial run is over after its last
+    /// statement or its first failure, a parallel run once every statement is in.
+    fn statement_done(&mut self, query_idx: usize, failed: bool) {
+        let run_over = match &mut self.parallel_remaining {
+            Some(remaining) => {
+                *remaining = remaining.saturating_sub(1);
+                *remaining == 0
+            }
+            None => (failed && !self.run_continues) || query_idx + 1 >= self.total_queries,
+        };
+        if run_over {
+            self.finish_run();
+        }
+    }
+
+    /// Mark the current run as over and summarise it
+    fn finish_run(&mut self) {
+        self.running = false;
+        self.running_query_idx = None;
+        self.parallel_remaining = None;
+        self.run_continues = false;
+        self.run_duration = self.run_started.map(|s| s.elapsed());
+        self.run_summary = self.run_summary_text();
+    }
+
+    /// "27 ok / 3 failed" for a multi-statement run, from its tabs
+    fn run_summary_text(&self) -> Option<String> {
+        if self.total_queries < 2 {
+            return None;
+        }
+        // one entry per statement, however many result sets it returned
+        let mut statements: BTreeMap<usize, bool> = BTreeMap::new();
+        for tab in self.results.tabs.iter().filter(|t| !t.running) {
+            if let Some(i) = tab.query_idx {
+                *statements.entry(i).or_default() |= tab.is_failed();
+            }
+        }
+        let failed = statements.values().filter(|&&f| f).count();
+        let ok = statements.len() - failed;
+        let mut text = format!("{} ok / {} failed", ok, failed);
+        let not_run = self.total_queries.saturating_sub(ok + failed);
+        if not_run > 0 {
+            text.push_str(&format!(", {} not run", not_run));
+        }
+        if failed > 0 {
+            text.push_str(" – { / } step through failed tabs");
+        }
+        Some(text)
+    }
+
+    /// Write a completed statement to the history database. A statement that
+    /// completes again after fetching more rows updates its existing entry.
+    fn record_history(&mut self, query_idx: usize, elapsed: Duration, row_count: Option<usize>, error: Option<&str>) {
+        let Some(history) = &self.history else { return };
+        let Some((sql, query_context)) = self.run_statements.get(query_idx) else { return };
+
+        if let Some(Some(id)) = self.history_ids.get(query_idx) {
+            let _ = history.update_result(*id, elapsed, row_count);
+            return;
+        }
+
+        let profile = self.active_profile.as_deref();
+        let role = self.session.as_ref()
+            .and_then(|s| s.role.as_deref())
+            .or_else(|| self.db_tree.cache.as_ref().and_then(|c| c.current_role.as_deref()))
+            .or_else(|| profile.and_then(|p| self.profiles.get(p)).and_then(|p| p.role.as_deref()));
+        let recorded = history.record(&CompletedStatement {
+            sql,
+            query_context,
+            profile,
+            role,
+            elapsed: Some(elapsed),
+            row_count,
+            error,
+        });
+        match recorded {
+            Ok(id) => {
+                if let Some(slot) = self.history_ids.get_mut(query_idx) {
+                    *slot = Some(id);
+                }
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Failed to write query history: {}", e));
+                self.status_message_time = Some(Instant::now());
+            }
+        }
+    }
+
     /// Call this from your main event loop regularly.
     pub fn poll_db_responses(&mut self) -> bool {
         let mut changed = false;
@@ -2642,68 +5231,233 @@ pub fn render<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> {
                 self.editor.schema_cache = Some(cache);
             }
         }
+        for old in std::mem::take(&mut self.old_resp_rxs) {
+            loop {
+                match old.try_recv() {
+                    Ok(DbWorkerResponse::ParkedSealed { path, result }) => {
+                        self.adopt_sealed(&path, result);
+                        changed = true;
+                    }
+                    Ok(_) => {}
+                    Err(mpsc::TryRecvError::Empty) => {
+                        self.old_resp_rxs.push(old);
+                        break;
+                    }
+                    Err(mpsc::TryRecvError::Disconnected) => break,
+                }
+            }
+        }
         while let Ok(msg) = self.db_resp_rx.try_recv() {
             match msg {
+                DbWorkerResponse::SessionContext(context) => {
+                    self.set_session_context(Some(context));
+                    changed = true;
+                }
                 DbWorkerResponse::Connected => {
-                    self.connected = true;
+                    let reconnected = !matches!(self.connection, ConnectionState::Connecting);
+                    if reconnected && self.transaction.is_some() {
+                        // the new connection is in autocommit; put it back in manual mode
+                        let _ = self.db_req_tx.send(DbWorkerRequest::SetTransactionMode { manual: true });
+                    }
+                    self.connection = ConnectionState::Connected;
                     self.db_tree.set_connected(true);
-                    self.status_message = Some("Connected to Snowflake".to_string());
+                    self.status_message = Some(match (&self.active_profile, reconnected) {
+                        (Some(name), false) => format!("Connected ({})", name),
+                        (Some(name), true) => format!("Reconnected ({})", name),
+                        (None, false) => "Connected to Snowflake".to_string(),
+                        (None, true) => "Reconnected to Snowflake".to_string(),
+                    });
+                    self.status_message_time = Some(Instant::now());
+                    changed = true;
+                }
+                DbWorkerResponse::Disconnected { message, retry_in } => {
+                    self.db_tree.set_connected(false);
+                    self.status_message = Some(format!(
+                        "Connection failed: {}",
+                        message.lines().next().unwrap_or("")
+                    ));
+                    self.status_message_time = Some(Instant::now());
+                    self.connection = ConnectionState::Disconnected {
+                        message,
+                        retry_at: retry_in.map(|d| Instant::now() + d),
+                    };
+                    changed = true;
+                }
+                DbWorkerResponse::Reconnecting { attempt } => {
+                    self.db_tree.set_connected(false);
+                    self.connection = ConnectionState::Reconnecting { attempt };
+                    changed = true;
+                }
+                DbWorkerResponse::TransactionModeChanged { manual } => {
+                    let lost = self.transaction.unwrap_or(0);
+                    self.transaction = manual.then_some(0);
+                    self.status_message = Some(match (manual, lost) {
+                        (true, 0) => "Manual commit mode: F7 commits, F8 rolls back".to_string(),
+                        (true, n) => format!("Connection was lost; {} uncommitted statement(s) were rolled back", n),
+                        (false, _) => "Autocommit mode".to_string(),
+                    });
                     self.status_message_time = Some(Instant::now());
                     changed = true;
                 }
-                DbWorkerResponse::QueryStarted { query_idx: _, started, query_context } => {
-                    // Add a tab for this query
-                    self.results.tabs.push(crate::results::ResultsTab::new_pending_with_start(query_context, started));
-                    self.results.tab_idx = self.results.tabs.len() - 1;
-                    self.running_query_idx = Some(self.results.tabs.len() - 1);
+                DbWorkerResponse::TransactionEnded { committed } => {
+                    if self.transaction.is_some() {
+                        self.transaction = Some(0);
+                    }
+                    self.status_message = Some(if committed { "Transaction committed" } else { "Transaction rolled back" }.to_string());
+                    self.status_message_time = Some(Instant::now());
+                    match self.after_transaction.take() {
+                        Some(AfterTransaction::Exit) => {
+                            if self.request_exit() {
+                                self.exit_ready = true;
+                            }
+                        }
+                        Some(AfterTransaction::SwitchProfile(name)) => self.switch_profile(&name),
+                        None => {}
+                    }
+                    changed = true;
+                }
+                DbWorkerResponse::TransactionError { message } => {
+                    self.after_transaction = None;
+                    self.status_message = Some(format!("Transaction error: {}", message.lines().next().unwrap_or("")));
+                    self.status_message_time = Some(Instant::now());
+                    changed = true;
+                }
+                DbWorkerResponse::QueryStarted { query_idx, started, query_context } => {
+                    if let Some(count) = &mut self.transaction {
+                        *count += 1;
+                    }
+                    // Keep the run's tabs in statement order (parallel statements can
+                    // start out of order), ahead of the queued runs
+                    let pos = self.results.tabs.iter()
+                        .position(|t| t.is_queued() || t.query_idx.is_some_and(|i| i > query_idx))
+                        .unwrap_or(self.results.tabs.len());
+                    let mut tab = ResultsTab::new_pending_with_start(query_context, started);
+                    tab.query_idx = Some(query_idx);
+                    tab.sql = self.run_statements.get(query_idx).map(|(sql, _)| sql.clone());
+                    self.results.tabs.insert(pos, tab);
+                    if self.parallel_remaining.is_none() || query_idx == 0 {
+                        self.results.tab_idx = pos;  // follow a serial run
+                    } else if self.results.tab_idx >= pos {
+                        self.results.tab_idx += 1;
+                    }
+                    self.running_query_idx = Some(pos);
                     self.running = true;
                     changed = true;
                 }
-                DbWorkerResponse::QueryFinished { query_idx, elapsed, result } => {
-                    if let Some(tab) = self.results.tabs.get_mut(query_idx) {
+                DbWorkerResponse::QueryStreaming { query_idx, result } => {
+                    if let Some(tab) = self.run_tab(query_idx) {
+                        tab.content = result;
+                    }
+                    changed = true;
+                }
+                DbWorkerResponse::QueryNextResultSet { query_idx, result, query_id } => {
+                    self.add_result_set_tab(query_idx, result, query_id);
+                    changed = true;
+                }
+                DbWorkerResponse::QueryProgress { query_idx, rows_fetched } => {
+                    if let Some(tab) = self.run_tab(query_idx) {
+                        tab.rows_fetched = Some(rows_fetched);
+                        if let ResultsContent::Table { tile_store, .. } = &mut tab.content {
+                            if tile_store.sync() {
+                                tab.summary_cache = None;
+                            }
+                        }
+                    }
+                    changed = true;
+                }
+                DbWorkerResponse::QueryFinished { query_idx, elapsed, result, query_id } => {
+                    self.record_history(query_idx, elapsed, Self::result_row_count(&result), None);
+                    if let Some(tab) = self.run_tab(query_idx) {
                         tab.content = result;
+                        tab.query_id = query_id;
                         tab.elapsed = Some(elapsed);
                         tab.running = false;
                         tab.run_started = None;
+                        tab.rows_fetched = None;
+                        tab.summary_cache = None;
                     }
-                    if query_idx + 1 < self.total_queries {
-                        // waiting for next QueryStarted to push the next tab
-                    } else {
-                        self.running = false;
-                        self.running_query_idx = None;
-                        self.run_duration = self.run_started.map(|s| s.elapsed());
+                    self.statement_done(query_idx, false);
+                    changed = true;
+                }
+                DbWorkerResponse::QueryTruncated { query_idx, elapsed, result, resumable, query_id } => {
+                    self.record_history(query_idx, elapsed, Self::result_row_count(&result), None);
+                    if let Some(tab) = self.run_tab(query_idx) {
+                        tab.content = result;
+                        tab.query_id = query_id;
+                        tab.elapsed = Some(elapsed);
+                        tab.running = false;
+                        tab.run_started = None;
+                        tab.rows_fetched = None;
+                        tab.truncated = Some(if resumable { Truncation::Resumable } else { Truncation::Closed });
+                        tab.summary_cache = None;
+                    }
+                    self.statement_done(query_idx, false);
+                    changed = true;
+                }
+                DbWorkerResponse::QueryTimedOut { query_idx, elapsed, timeout } => {
+                    let message = format!("Statement timed out after {}s", timeout.as_secs());
+                    self.record_history(query_idx, elapsed, None, Some(&message));
+                    if let Some(tab) = self.run_tab(query_idx) {
+                        tab.content = ResultsContent::TimedOut { timeout };
+                        tab.elapsed = Some(elapsed);
+                        tab.running = false;
+                        tab.run_started = None;
+                        tab.rows_fetched = None;
                     }
+
+                    // A serial run skips later statements, same as after an error
+                    self.statement_done(query_idx, true);
+                    self.status_message = Some(message);
+                    self.status_message_time = Some(Instant::now());
                     changed = true;
                 }
-                DbWorkerResponse::QueryError { query_idx, elapsed, message } => {
+                DbWorkerResponse::QueryError { query_idx, elapsed, message, query_id } => {
                     // Was it a user-cancel?  ODBC returns SQLSTATE HY008 (“Operation cancelled”)
                     let is_cancel = message.contains("HY008");
+                    self.record_history(query_idx, elapsed, None, Some(if is_cancel { "Cancelled" } else { &message }));
 
-                    if let Some(tab) = self.results.tabs.get_mut(query_idx) {
+                    let parsed = SqlError::parse(&message);
+                    let location = parsed.position.and_then(|pos| self.error_location(query_idx, pos));
+                    if let Some(tab) = self.run_tab(query_idx) {
                         if is_cancel {
                             tab.content = ResultsContent::Info { message: "Cancelled.".to_string() };
                         } else {
                             tab.content = ResultsContent::Error { 
-                                message: message.clone(),
+                                message: parsed.display_text(location.as_ref()),
                                 cursor: 0,
                                 selection: None,
+                                location,
                             };
                         }
+                        tab.query_id = query_id;
                         tab.elapsed = Some(elapsed);
                         tab.running = false;
                         tab.run_started = None;
                     }
 
                     // Clear running state so UI unlocks
-                    self.running = false;
-                    self.running_query_idx = None;
-                    self.run_duration = self.run_started.map(|s| s.elapsed());
+                    self.statement_done(query_idx, true);
 
                     if !is_cancel {
                         self.error = Some(message);          // real error still shown in status
                     }
                     changed = true;
                 }
+                DbWorkerResponse::ParkedSealed { path, result } => {
+                    self.adopt_sealed(&path, result);
+                    changed = true;
+                }
+                DbWorkerResponse::Idle => {
+                    self.worker_busy = false;
+                    // The worker is done with the run even if `statement_done`
+                    // didn't see its end, e.g. a continue-on-error run cut
+                    // short by a lost connection or a cancelled parallel run
+                    if self.running {
+                        self.finish_run();
+                    }
+                    self.start_queued_run();
+                    changed = true;
+                }
             }
             // Update editor's schema cache when tree is refreshed
             if let Some(cache) = self.db_tree.cache.clone() {
@@ -2715,21 +5469,20 @@ pub fn render<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> {
 
     pub fn export_csv_to_downloads(&mut self) {
         use crate::results::ResultsContent;
-        use crate::results_export::export_entire_result_set;
+        use crate::results_export::write_entire_result_set;
 
         let filename = "results_export.csv";
         let download_folder = get_downloads_folder();
         let target_path = download_folder.join(filename);
 
         if let Some(tab) = self.results.tabs.get_mut(self.results.tab_idx) {
-            if let ResultsContent::Table { headers, tile_store } = &mut tab.content {
-                // tile_store is now &mut TileRowStore
-                let data = match tile_store.get_rows(0, tile_store.nrows) {
-                    Ok(rows) => rows,
-                    Err(_) => vec![],
-                };
-                let csv = export_entire_result_set(headers, &data);
-                match std::fs::write(&target_path, csv) {
+            if let ResultsContent::Table { headers, tile_store, .. } = &mut tab.content {
+                let written = std::fs::File::create(&target_path).and_then(|file| {
+                    let mut out = std::io::BufWriter::new(file);
+                    write_entire_result_set(&mut out, headers, tile_store)?;
+                    std::io::Write::flush(&mut out)
+                });
+                match written {
                     Ok(_) => {
                         self.status_message = Some(format!("CSV exported to: {}", target_path.display()));
                         self.status_message_time = Some(Instant::now());